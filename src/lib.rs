@@ -1,3 +1,4 @@
 pub mod rc;
 pub mod sync;
+mod topic;
 pub mod types;
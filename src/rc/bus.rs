@@ -5,17 +5,28 @@
 */
 #![allow(dead_code)]
 use crate::{
-    rc::{types::*, Event, Subscriber},
+    rc::{
+        queue::{Command, DispatchQueue},
+        types::*,
+        Event, Subscriber, Subscription,
+    },
+    topic::{filter_matches, parse_filter, TopicTrie},
     types::*,
 };
-use std::collections::{BTreeMap, HashMap};
+use std::cell::RefCell;
+use std::collections::{BTreeMap, HashMap, HashSet, VecDeque};
 use std::hash::Hash;
-use std::rc::{Rc, Weak};
+use std::rc::Rc;
+use std::slice;
 
 /// Single-thread datastructure responsible for dispatching events from `Publisher`s to `Subscriber`s
 ///
 /// This keeps the respective Pub/Sub systems decoupled from each other
 ///
+/// `Subscriber`s of a category are notified in the order they subscribed in, followed by those subscribed to every category (see `subscribe_all`)
+///
+/// A bus created through `hierarchical` also hands each event to the `Subscriber`s of every ancestor of its category (see `HierarchicalCategory`)
+///
 /// This should be wrapped in a Rc<RefCell<EventBus>>
 pub struct EventBus<T, E>
 where
@@ -25,6 +36,26 @@ where
     // We hold a std::rc::Weak (Rc which holds non-owning reference) to not prevent dropping and to avoid circular references to an Rc
     // We can deal with subscribers that get dropped by just removing them from our map if we find they did get dropped
     channels: SubscriberMap<T, E>,
+    // Subscribers handed every event, whatever its category (see `subscribe_all`)
+    catch_all: Vec<SubscriberRef<T, E>>,
+    // How events are routed through the ancestors of their category, if this bus was created through `hierarchical`
+    routing: Option<Routing<T>>,
+    // How subscribers which panic are dealt with, if this bus was set up through `isolating_panics`
+    isolation: Option<Isolation>,
+    // Categories whose latest event is kept around for late subscribers (see `make_sticky`)
+    sticky_categories: HashSet<T>,
+    // The latest sticky event of each category, handed to every subscriber which subscribes to that category later on
+    sticky: HashMap<T, E>,
+    // Where events which don't reach a single subscriber are handed, if this bus was set up through `collecting_dead_letters`
+    dead_letters: Option<DeadLetterSink<E>>,
+    // The latest events dispatched to each category, if this bus was set up through `recording_history`
+    history: Option<History<T, E, EventDispatchResult>>,
+    // The ids of `Subscription`s dropped since we last removed their entries (see `remove_dropped`)
+    dropped: DroppedSubscriptions,
+    // Commands deferred by subscribers until we've finished dispatching the current event
+    deferred: DispatchQueue<Self, E>,
+    // Events waiting to be dispatched by `drain` or `pump`, in the order they were queued in
+    queued: VecDeque<E>,
 }
 
 impl<T, E> Default for EventBus<T, E>
@@ -35,6 +66,16 @@ where
     fn default() -> Self {
         Self {
             channels: HashMap::default(),
+            catch_all: Vec::new(),
+            routing: None,
+            isolation: None,
+            sticky_categories: HashSet::new(),
+            sticky: HashMap::new(),
+            dead_letters: None,
+            history: None,
+            dropped: DroppedSubscriptions::default(),
+            deferred: DispatchQueue::default(),
+            queued: VecDeque::new(),
         }
     }
 }
@@ -44,48 +85,295 @@ where
     T: Eq + PartialEq + Hash + Clone + 'static,
     E: Event<T> + Eq + PartialEq + Hash + Clone + 'static,
 {
+    /// Creates an `EventBus` which also hands each event to the `Subscriber`s of every ancestor of its category, in the given order
+    pub fn hierarchical(order: RoutingOrder) -> Self
+    where
+        T: HierarchicalCategory,
+    {
+        Self {
+            routing: Some(Routing::new(order)),
+            ..Self::default()
+        }
+    }
+
+    /// Has this `EventBus` catch any panic a `Subscriber` throws while handling an event, rather than letting it unwind through the whole dispatch, and deal with that `Subscriber` as the given policy says (see `PanicPolicy`)
+    pub fn isolating_panics(mut self, policy: PanicPolicy) -> Self {
+        self.isolation = Some(Isolation::new(policy));
+        self
+    }
+
+    /// The id of every `Subscriber` quarantined after panicking (see `PanicPolicy::Quarantine`), in the order they were quarantined in
+    pub fn quarantined(&self) -> Vec<SubscriberId> {
+        self.isolation
+            .as_ref()
+            .map_or_else(Vec::new, Isolation::quarantined)
+    }
+
+    /// Lets the quarantined `Subscriber` with the given id be handed events again
+    ///
+    /// ### Returns
+    /// - `bool`: whether the `Subscriber` was quarantined at all.
+    pub fn release(&mut self, id: SubscriberId) -> bool {
+        self.isolation
+            .as_mut()
+            .map_or(false, |isolation| isolation.release(id))
+    }
+
+    /// Has this `EventBus` hand every event which doesn't reach a single `Subscriber` to the given sink, along with why it didn't (see `DeadLetter`)
+    pub fn collecting_dead_letters<F: Fn(&E, DeadLetter) + 'static>(mut self, sink: F) -> Self {
+        self.dead_letters = Some(Box::new(sink));
+        self
+    }
+
+    /// Has this `EventBus` keep the latest `capacity` events dispatched to each category, along with what became of them (see `HistoryEntry`)
+    pub fn recording_history(mut self, capacity: usize) -> Self {
+        self.history = Some(History::new(capacity));
+        self
+    }
+
+    /// The latest events dispatched to the given category, oldest first, if this `EventBus` was set up through `recording_history`
+    pub fn history(&self, category: &T) -> Vec<&HistoryEntry<E, EventDispatchResult>> {
+        self.history
+            .as_ref()
+            .map_or_else(Vec::new, |history| history.entries(category))
+    }
+
     /// Adds the given `Subscriber` to a subscriber list to receive published messages of the given event category
-    pub fn subscribe<S: Subscriber<T, E> + 'static>(&mut self, subscriber: &Rc<S>, to_category: T) {
+    ///
+    /// ### Returns
+    /// - `Subscription`: a guard which unsubscribes the `Subscriber` once it's dropped, call `detach` on it to keep the `Subscriber` subscribed instead.
+    pub fn subscribe<S: Subscriber<T, E> + 'static>(
+        &mut self,
+        subscriber: &Rc<S>,
+        to_category: T,
+    ) -> Subscription {
+        let subscriber = SubscriberRef::shared(Rc::downgrade(
+            &(subscriber.clone() as Rc<dyn Subscriber<T, E> + 'static>),
+        ));
+        let subscription = subscriber.subscription(&self.dropped);
+        self.add(subscriber, to_category);
+        subscription
+    }
+
+    /// Adds the given `Subscriber` to a subscriber list to receive published messages of the given event category through `on_event_mut`
+    ///
+    /// ### Returns
+    /// - `Subscription`: a guard which unsubscribes the `Subscriber` once it's dropped, call `detach` on it to keep the `Subscriber` subscribed instead.
+    ///
+    /// ### Notes
+    /// - A `Subscriber` which is already borrowed when an event is dispatched to it (i.e. because it's the one that dispatched the event) will not receive the event.
+    pub fn subscribe_mut<S: Subscriber<T, E> + 'static>(
+        &mut self,
+        subscriber: &Rc<RefCell<S>>,
+        to_category: T,
+    ) -> Subscription {
+        let subscriber = SubscriberRef::mutable(Rc::downgrade(
+            &(subscriber.clone() as Rc<RefCell<dyn Subscriber<T, E> + 'static>>),
+        ));
+        let subscription = subscriber.subscription(&self.dropped);
+        self.add(subscriber, to_category);
+        subscription
+    }
+
+    /// Adds the given `Subscriber` to a subscriber list to receive published messages of the given event category, but only those matching the given predicate
+    ///
+    /// ### Returns
+    /// - `Subscription`: a guard which unsubscribes the `Subscriber` once it's dropped, call `detach` on it to keep the `Subscriber` subscribed instead.
+    ///
+    /// ### Notes
+    /// - The predicate is checked before the `Subscriber` is handed each event, and an event it rejects is never delivered: it shows up in a `DispatchReport`'s `filtered` rather than its `received`, and doesn't count towards the dispatch result.
+    pub fn subscribe_filtered<S: Subscriber<T, E> + 'static, F: Fn(&E) -> bool + 'static>(
+        &mut self,
+        subscriber: &Rc<S>,
+        to_category: T,
+        predicate: F,
+    ) -> Subscription {
+        let subscriber = SubscriberRef::shared(Rc::downgrade(
+            &(subscriber.clone() as Rc<dyn Subscriber<T, E> + 'static>),
+        ))
+        .filtered_by(predicate);
+        let subscription = subscriber.subscription(&self.dropped);
+        self.add(subscriber, to_category);
+        subscription
+    }
+
+    /// Adds the given `Subscriber` to the subscriber lists of each of the given event categories at once
+    ///
+    /// ### Returns
+    /// - `Subscription`: a single guard for every one of those subscriptions, which unsubscribes the `Subscriber` from all of them once it's dropped. They all share its id, so `unsubscribe` still unsubscribes the `Subscriber` from one category at a time.
+    pub fn subscribe_many<S: Subscriber<T, E> + 'static, I: IntoIterator<Item = T>>(
+        &mut self,
+        subscriber: &Rc<S>,
+        to_categories: I,
+    ) -> Subscription {
+        let subscriber = SubscriberRef::shared(Rc::downgrade(
+            &(subscriber.clone() as Rc<dyn Subscriber<T, E> + 'static>),
+        ));
+        let subscription = subscriber.subscription(&self.dropped);
+        for to_category in to_categories {
+            self.add(subscriber.clone(), to_category);
+        }
+        subscription
+    }
+
+    /// Adds the given `Subscriber` to receive every event published on this `EventBus`, whatever its category
+    ///
+    /// ### Notes
+    /// - It's notified after the `Subscriber`s of the event's category, even if one of those halted propagation. Halting propagation itself only keeps the event from the rest of the `Subscriber`s subscribed to every category.
+    /// - Unlike any other `Subscriber`, it isn't subscribed to a category it can be unsubscribed from, so it stays subscribed until its `Subscription` is dropped, `unsubscribe_everywhere` is called for it, or it's dropped.
+    ///
+    /// ### Returns
+    /// - `Subscription`: a guard which unsubscribes the `Subscriber` once it's dropped, call `detach` on it to keep the `Subscriber` subscribed instead.
+    pub fn subscribe_all<S: Subscriber<T, E> + 'static>(
+        &mut self,
+        subscriber: &Rc<S>,
+    ) -> Subscription {
+        self.remove_dropped();
+        let subscriber = SubscriberRef::shared(Rc::downgrade(
+            &(subscriber.clone() as Rc<dyn Subscriber<T, E> + 'static>),
+        ));
+        let subscription = subscriber.subscription(&self.dropped);
+        self.catch_all.push(subscriber);
+        subscription
+    }
+
+    /// Subscribes the given closure to receive published messages of the given event category, with this `EventBus` taking ownership of it
+    ///
+    /// ### Returns
+    /// - `FnHandle<T>`: a handle which can be handed to `unsubscribe_fn` to unsubscribe the closure again.
+    pub fn subscribe_fn<F: Fn(&E) -> BusRequest + 'static>(
+        &mut self,
+        to_category: T,
+        function: F,
+    ) -> FnHandle<T> {
+        let subscriber = SubscriberRef::owned(Rc::new(FnSubscriber::new(function)));
+        let handle = FnHandle {
+            id: subscriber.id(),
+            subscribed_to: to_category.clone(),
+        };
+        self.add(subscriber, to_category);
+        handle
+    }
+
+    /// Adds the given `Subscriber` to a subscriber list to receive published messages of the given event category, handing it the latest `last` events dispatched to that category first, oldest first (see `HistoryEntry`)
+    ///
+    /// ### Notes
+    /// - The category's sticky event (see `make_sticky`) is only handed to the `Subscriber` if there are no events to replay, as it's the latest of them otherwise.
+    ///
+    /// ### Returns
+    /// - `Subscription`: a guard which unsubscribes the `Subscriber` once it's dropped, call `detach` on it to keep the `Subscriber` subscribed instead.
+    pub fn subscribe_with_replay<S: Subscriber<T, E> + 'static>(
+        &mut self,
+        subscriber: &Rc<S>,
+        to_category: T,
+        last: usize,
+    ) -> Subscription {
+        let subscriber = SubscriberRef::shared(Rc::downgrade(
+            &(subscriber.clone() as Rc<dyn Subscriber<T, E> + 'static>),
+        ));
+        let subscription = subscriber.subscription(&self.dropped);
+        let events = self.history.as_ref().map_or_else(Vec::new, |history| {
+            history.latest(|category| *category == to_category, last)
+        });
+        if events.is_empty() {
+            self.add(subscriber, to_category);
+        } else if subscriber.replay(&events, self.isolation.as_mut()) {
+            self.insert(subscriber, to_category);
+        }
+        subscription
+    }
+
+    fn add(&mut self, subscriber: SubscriberRef<T, E>, to_category: T) {
+        // A late subscriber is handed the category's sticky event first, and doesn't stay subscribed if that's all it wanted
+        if let Some(event) = self.sticky.get(&to_category) {
+            if !subscriber.replay(slice::from_ref(event), self.isolation.as_mut()) {
+                return;
+            }
+        }
+        self.insert(subscriber, to_category);
+    }
+
+    fn insert(&mut self, subscriber: SubscriberRef<T, E>, to_category: T) {
+        self.remove_dropped();
         if let Some(subscriber_list) = self.channels.get_mut(&to_category) {
             // We have an existing subscriber list for this category, push a new subscriber to it
-            subscriber_list.push(Rc::downgrade(
-                &(subscriber.clone() as Rc<dyn Subscriber<T, E> + 'static>),
-            ));
+            subscriber_list.push(subscriber);
         } else {
             // No subscriber list exists yet for this category, create one
-            self.channels.insert(
-                to_category,
-                vec![Rc::downgrade(
-                    &(subscriber.clone() as Rc<dyn Subscriber<T, E> + 'static>),
-                )],
-            );
+            self.channels.insert(to_category, vec![subscriber]);
+        }
+    }
+
+    /// Removes the entries of every `Subscription` dropped since we last did, so they're gone before we next touch our subscriber lists
+    fn remove_dropped(&mut self) {
+        let dropped = self.dropped.take();
+        if dropped.is_empty() {
+            return;
         }
+        for subscriber_list in self.channels.values_mut() {
+            subscriber_list.retain(|subscriber| !dropped.contains(&subscriber.id()));
+        }
+        self.catch_all
+            .retain(|subscriber| !dropped.contains(&subscriber.id()));
     }
 
-    /// Unsubscribes the given `Subscriber` from the given category on this `EventBus`
+    /// Unsubscribes the `Subscriber` with the given id (see `Subscription::id`) from the given category on this `EventBus`
     ///
     /// ### Notes
     /// - Automatically removes any dropped subscribers in the channel corresponding to the given category, if the bus encounters any.
-    pub fn unsubscribe<S: Subscriber<T, E> + 'static>(&mut self, subscriber: &S, from_category: T) {
-        let mut cleanup_required = false;
+    ///
+    /// ### Returns
+    /// - `UnsubscribeOutcome`: whether the `Subscriber` was removed, wasn't subscribed to the given category, or had already been dropped.
+    pub fn unsubscribe(&mut self, id: SubscriberId, from_category: T) -> UnsubscribeOutcome {
+        self.remove_dropped();
+        let mut outcome = UnsubscribeOutcome::NotFound;
         if let Some(subscriber_list) = self.channels.get_mut(&from_category) {
-            if let Some(idx) = subscriber_list.iter().position(|weak_sub| {
-                if let Some(sub) = weak_sub.upgrade() {
-                    sub.id() == subscriber.id()
+            if let Some(idx) = subscriber_list
+                .iter()
+                .position(|subscriber| subscriber.id() == id)
+            {
+                // Subscribers are notified in the order they subscribed in, so the rest of the list must keep its order
+                outcome = if subscriber_list.remove(idx).is_dropped() {
+                    UnsubscribeOutcome::AlreadyDead
                 } else {
-                    // We dropped a subscriber, need to clean up
-                    cleanup_required = true;
-                    false
-                }
-            }) {
-                // We can swap_remove for O(1) performance here because we don't care about ordering
-                subscriber_list.swap_remove(idx);
+                    UnsubscribeOutcome::Removed
+                };
             }
 
-            if cleanup_required {
-                subscriber_list.retain(|susbcriber| Weak::clone(susbcriber).upgrade().is_some());
+            if subscriber_list
+                .iter()
+                .any(|subscriber| subscriber.is_dropped())
+            {
+                subscriber_list.retain(|subscriber| !subscriber.is_dropped());
             }
         }
+        outcome
+    }
+
+    /// Unsubscribes a closure subscribed through `subscribe_fn` from this `EventBus`, dropping it (see `unsubscribe`)
+    pub fn unsubscribe_fn(&mut self, handle: &FnHandle<T>) -> UnsubscribeOutcome {
+        self.unsubscribe(handle.id, handle.subscribed_to.clone())
+    }
+
+    /// Unsubscribes the given `Subscriber` from every category on this `EventBus` it's subscribed to (including all of them at once, see `subscribe_all`), however many times it's subscribed to each
+    ///
+    /// ### Notes
+    /// - `subscriber` is the `Rc` the `Subscriber` was subscribed through, i.e. an `Rc<RefCell<S>>` for one subscribed through `subscribe_mut`.
+    ///
+    /// ### Returns
+    /// - `usize`: the number of subscriptions which were removed.
+    pub fn unsubscribe_everywhere<S: ?Sized>(&mut self, subscriber: &Rc<S>) -> usize {
+        self.remove_dropped();
+        let mut removed = 0;
+        for subscriber_list in self.channels.values_mut() {
+            let before = subscriber_list.len();
+            subscriber_list.retain(|entry| !entry.refers_to(subscriber));
+            removed += before - subscriber_list.len();
+        }
+        let before = self.catch_all.len();
+        self.catch_all.retain(|entry| !entry.refers_to(subscriber));
+        removed += before - self.catch_all.len();
+        removed
     }
 
     /// Removes all `Subscriber`s from this `EventBus`
@@ -93,47 +381,227 @@ where
     /// ### Notes
     /// - The memory previously allocated for the `Subscriber`s remains allocated for reuse.
     pub fn unsubscribe_all(&mut self) {
-        self.channels.clear()
+        self.remove_dropped();
+        self.channels.clear();
+        self.catch_all.clear();
     }
 
     /// Removes all `Subscriber`s from the given category on this `EventBus`
     pub fn unsubscribe_all_from_category(&mut self, from_category: T) {
+        self.remove_dropped();
         self.channels.remove(&from_category);
     }
 
+    /// Marks the given category as sticky, so that the latest event dispatched to it is kept around and handed to every `Subscriber` which subscribes to it later on
+    ///
+    /// ### Notes
+    /// - An event is kept for every category it's routed to (see `hierarchical`), and events which are sticky themselves (see `Event::is_sticky`) are kept whatever their category.
+    /// - A `Subscriber` is handed the sticky event right away when it subscribes, and it isn't subscribed at all if it answers with `BusRequest::Unsubscribe`.
+    /// - `Subscriber`s subscribed to every category (see `subscribe_all`) aren't handed sticky events.
+    pub fn make_sticky(&mut self, category: T) {
+        self.sticky_categories.insert(category);
+    }
+
+    /// The sticky event kept for the given category, if there is one (see `make_sticky`)
+    pub fn sticky_event(&self, category: &T) -> Option<&E> {
+        self.sticky.get(category)
+    }
+
+    /// Forgets the sticky event kept for the given category, if there is one, so late `Subscriber`s aren't handed it any longer
+    ///
+    /// ### Notes
+    /// - The category itself stays sticky, so the next event dispatched to it is kept again.
+    pub fn clear_sticky(&mut self, category: &T) -> Option<E> {
+        self.sticky.remove(category)
+    }
+
+    /// Forgets every sticky event, and stops keeping new ones for any category marked by `make_sticky`
+    pub fn clear_all_sticky(&mut self) {
+        self.sticky.clear();
+        self.sticky_categories.clear();
+    }
+
+    /// Keeps the given event for each category it's routed to which is sticky, or for every one of them if the event itself is
+    fn keep_sticky(&mut self, event: &E) {
+        if self.sticky_categories.is_empty() && !event.is_sticky() {
+            return;
+        }
+        for category in route(&self.routing, event.categories()) {
+            if event.is_sticky() || self.sticky_categories.contains(&category) {
+                self.sticky.insert(category, event.clone());
+            }
+        }
+    }
+
     /// Dispatches the given event to all `Subscriber`s of that event's category
     ///
     /// ### Notes
     /// - Automatically removes any dropped `Subscriber`s in the channel the given event belongs to, if the bus encounters any.
     pub fn dispatch_event(&mut self, event: &E) -> EventDispatchResult {
-        let mut result = EventDispatchResult::NotNeeded;
-        // Grab our list of subscribers for this event's category, if one exists
-        if let Some(subscriber_list) = self.channels.get_mut(&event.category()) {
-            let mut cleanup_required = false;
-            // Attempt to have all subscribers handle the dispatched event and return requests to the event bus (non-blocking)
-            result = execute_bus_requests(subscriber_list, |weak_subscriber| {
-                if let Some(subscriber) = weak_subscriber.upgrade() {
-                    subscriber.on_event(event)
-                } else {
-                    // Found an invalid reference to a subscriber (which was probably dropped by the owner)
-                    cleanup_required = true;
-                    BusRequest::NoActionNeeded
-                }
-            });
+        let result = self.dispatch(event, None);
+        self.run_deferred();
+        result
+    }
+
+    /// Dispatches the given event to all `Subscriber`s of that event's category, noting down what became of each of them along the way
+    ///
+    /// ### Notes
+    /// - Automatically removes any dropped `Subscriber`s in the channel the given event belongs to, if the bus encounters any.
+    pub fn dispatch_event_with_report(
+        &mut self,
+        event: &E,
+    ) -> (EventDispatchResult, DispatchReport) {
+        let mut report = DispatchReport::default();
+        let result = self.dispatch(event, Some(&mut report));
+        self.run_deferred();
+        (result, report)
+    }
+
+    /// Queues the given event to be dispatched later on, by `drain` or `pump`, rather than right away
+    ///
+    /// ### Notes
+    /// - A `Subscriber` can't reach this bus while it's being dispatched to, so it queues events from inside `on_event` through its `DispatchQueue` instead (see `DispatchQueue::queue_event`).
+    pub fn queue_event(&mut self, event: E) {
+        self.queued.push_back(event);
+    }
+
+    /// The number of events queued by `queue_event` which are still waiting to be dispatched
+    pub fn queued_events(&self) -> usize {
+        self.queued.len()
+    }
+
+    /// Dispatches every queued event, in the order they were queued in
+    ///
+    /// ### Notes
+    /// - Automatically removes any dropped `Subscriber`s in the channels the queued events belong to, if the bus encounters any.
+    /// - Events queued along the way (see `DispatchQueue::queue_event`) are left queued for the next `drain` or `pump`.
+    ///
+    /// ### Returns
+    /// - `Vec<(E, EventDispatchResult)>`: every event that was dispatched, alongside the result of dispatching it.
+    pub fn drain(&mut self) -> Vec<(E, EventDispatchResult)> {
+        self.pump(self.queued.len())
+    }
+
+    /// Dispatches up to `max_events` of the queued events, in the order they were queued in, leaving the rest queued
+    ///
+    /// ### Notes
+    /// - Automatically removes any dropped `Subscriber`s in the channels the queued events belong to, if the bus encounters any.
+    /// - Events queued along the way (see `DispatchQueue::queue_event`) are left queued for the next `drain` or `pump`.
+    ///
+    /// ### Returns
+    /// - `Vec<(E, EventDispatchResult)>`: every event that was dispatched, alongside the result of dispatching it.
+    pub fn pump(&mut self, max_events: usize) -> Vec<(E, EventDispatchResult)> {
+        let count = max_events.min(self.queued.len());
+        let mut results = Vec::with_capacity(count);
+        for _ in 0..count {
+            if let Some(event) = self.queued.pop_front() {
+                let result = self.dispatch_event(&event);
+                results.push((event, result));
+            }
+        }
+        results
+    }
+
+    /// A handle onto this bus's queue of deferred commands, through which `Subscriber`s can publish events and change subscriptions from inside `on_event`
+    ///
+    /// ### Notes
+    /// - Commands queued while dispatching an event are run once this bus has finished dispatching it, in the order they were queued in.
+    pub fn dispatch_queue(&self) -> DispatchQueue<Self, E> {
+        self.deferred.clone()
+    }
 
-            if cleanup_required {
-                subscriber_list.retain(|susbcriber| Weak::clone(susbcriber).upgrade().is_some());
+    /// Runs every command deferred by our subscribers, including any deferred along the way, until there are none left
+    fn run_deferred(&mut self) {
+        while let Some(command) = self.deferred.pop() {
+            match command {
+                Command::Publish(event) => {
+                    self.dispatch(&event, None);
+                }
+                Command::Apply(command) => command(self),
             }
         }
+    }
 
+    /// Dispatches the given event through `dispatch_to_subscribers`, handing it to our dead-letter sink afterwards if it didn't reach a single subscriber, and recording it in our history
+    fn dispatch(&mut self, event: &E, report: Option<&mut DispatchReport>) -> EventDispatchResult {
+        self.remove_dropped();
+        self.keep_sticky(event);
+        let result = if self.dead_letters.is_none() {
+            self.dispatch_to_subscribers(event, report)
+        } else {
+            // Whether the event is a dead letter is told by what became of it, so we need a report of our own if we weren't asked for one
+            let mut own_report = DispatchReport::default();
+            let report = report.unwrap_or(&mut own_report);
+            let result = self.dispatch_to_subscribers(event, Some(&mut *report));
+            if let (Some(sink), Some(reason)) = (&self.dead_letters, report.dead_letter()) {
+                sink(event, reason);
+            }
+            result
+        };
+        if let Some(history) = self.history.as_mut() {
+            history.record(route(&self.routing, event.categories()), event, &result);
+        }
         result
     }
+
+    fn dispatch_to_subscribers(
+        &mut self,
+        event: &E,
+        mut report: Option<&mut DispatchReport>,
+    ) -> EventDispatchResult {
+        let mut handed = Handed::default();
+        let mut isolation = self.isolation.as_mut();
+        let mut outcome = None;
+        // Grab our list of subscribers for each category the event is routed to, if one exists, until propagation is halted
+        for category in route(&self.routing, event.categories()) {
+            if let Some(subscriber_list) = self.channels.get_mut(&category) {
+                // Have all subscribers handle the dispatched event and return requests to the event bus
+                if let Some((stopped, failures)) =
+                    execute_bus_requests(subscriber_list, report.as_deref_mut(), |subscriber| {
+                        handed.deliver(subscriber.address(), || {
+                            subscriber.deliver_isolated(event, isolation.as_deref_mut())
+                        })
+                    })
+                {
+                    let (_, earlier_failures) = outcome.unwrap_or((false, 0));
+                    outcome = Some((stopped, earlier_failures + failures));
+                    if stopped {
+                        // Whoever was handed the event in this category mustn't be handed it again by the catch-all pass below
+                        handed.next_category();
+                        break;
+                    }
+                }
+                handed.next_category();
+            }
+        }
+        // Subscribers of every category see the event even if propagation was halted before them
+        if !self.catch_all.is_empty() {
+            if let Some((_, failures)) =
+                execute_bus_requests(&mut self.catch_all, report, |subscriber| {
+                    handed.deliver(subscriber.address(), || {
+                        subscriber.deliver_isolated(event, isolation.as_deref_mut())
+                    })
+                })
+            {
+                let (stopped, earlier_failures) = outcome.unwrap_or((false, 0));
+                outcome = Some((stopped, earlier_failures + failures));
+            }
+        }
+        match outcome {
+            Some((stopped, failures)) => dispatch_result(stopped, failures),
+            None => EventDispatchResult::NotNeeded,
+        }
+    }
 }
 
 /// Single-thread datastructure responsible for dispatching events from `Publisher`s to `Subscriber`s in a prioritized order
 ///
 /// This keeps the respective Pub/Sub systems decoupled from each other
 ///
+/// `Subscriber`s subscribed to every category (see `subscribe_all`) are notified after every priority segment
+///
+/// A bus created through `hierarchical` also hands each event to the `Subscriber`s of every ancestor of its category (see `HierarchicalCategory`)
+///
 /// This should be wrapped in a Rc<RefCell<PriorityEventBus>>
 pub struct PriorityEventBus<T, E, P>
 where
@@ -142,6 +610,20 @@ where
     P: Ord,
 {
     channels: PrioritySubscriberMap<T, E, P>,
+    // Subscribers handed every event, whatever its category (see `subscribe_all`)
+    catch_all: Vec<SubscriberRef<T, E>>,
+    // How events are routed through the ancestors of their category, if this bus was created through `hierarchical`
+    routing: Option<Routing<T>>,
+    // How subscribers which panic are dealt with, if this bus was set up through `isolating_panics`
+    isolation: Option<Isolation>,
+    // Where events which don't reach a single subscriber are handed, if this bus was set up through `collecting_dead_letters`
+    dead_letters: Option<DeadLetterSink<E>>,
+    // The latest events dispatched to each category, if this bus was set up through `recording_history`
+    history: Option<History<T, E, EventDispatchResult>>,
+    // The ids of `Subscription`s dropped since we last removed their entries (see `remove_dropped`)
+    dropped: DroppedSubscriptions,
+    // Commands deferred by subscribers until we've finished dispatching the current event
+    deferred: DispatchQueue<Self, E>,
 }
 
 impl<T, E, P> Default for PriorityEventBus<T, E, P>
@@ -153,6 +635,13 @@ where
     fn default() -> Self {
         Self {
             channels: HashMap::default(),
+            catch_all: Vec::new(),
+            routing: None,
+            isolation: None,
+            dead_letters: None,
+            history: None,
+            dropped: DroppedSubscriptions::default(),
+            deferred: DispatchQueue::default(),
         }
     }
 }
@@ -163,92 +652,337 @@ where
     E: Event<T> + Eq + PartialEq + Hash + Clone + 'static,
     P: Ord,
 {
+    /// Creates a `PriorityEventBus` which also hands each event to the `Subscriber`s of every ancestor of its category, in the given order
+    ///
+    /// ### Notes
+    /// - Every priority segment of one category is dispatched to before any of the next category's.
+    pub fn hierarchical(order: RoutingOrder) -> Self
+    where
+        T: HierarchicalCategory,
+    {
+        Self {
+            routing: Some(Routing::new(order)),
+            ..Self::default()
+        }
+    }
+
+    /// Has this `PriorityEventBus` catch any panic a `Subscriber` throws while handling an event, rather than letting it unwind through the whole dispatch, and deal with that `Subscriber` as the given policy says (see `PanicPolicy`)
+    pub fn isolating_panics(mut self, policy: PanicPolicy) -> Self {
+        self.isolation = Some(Isolation::new(policy));
+        self
+    }
+
+    /// The id of every `Subscriber` quarantined after panicking (see `PanicPolicy::Quarantine`), in the order they were quarantined in
+    pub fn quarantined(&self) -> Vec<SubscriberId> {
+        self.isolation
+            .as_ref()
+            .map_or_else(Vec::new, Isolation::quarantined)
+    }
+
+    /// Lets the quarantined `Subscriber` with the given id be handed events again
+    ///
+    /// ### Returns
+    /// - `bool`: whether the `Subscriber` was quarantined at all.
+    pub fn release(&mut self, id: SubscriberId) -> bool {
+        self.isolation
+            .as_mut()
+            .map_or(false, |isolation| isolation.release(id))
+    }
+
+    /// Has this `PriorityEventBus` hand every event which doesn't reach a single `Subscriber` to the given sink, along with why it didn't (see `DeadLetter`)
+    pub fn collecting_dead_letters<F: Fn(&E, DeadLetter) + 'static>(mut self, sink: F) -> Self {
+        self.dead_letters = Some(Box::new(sink));
+        self
+    }
+
+    /// Has this `PriorityEventBus` keep the latest `capacity` events dispatched to each category, along with what became of them (see `HistoryEntry`)
+    pub fn recording_history(mut self, capacity: usize) -> Self {
+        self.history = Some(History::new(capacity));
+        self
+    }
+
+    /// The latest events dispatched to the given category, oldest first, if this `PriorityEventBus` was set up through `recording_history`
+    pub fn history(&self, category: &T) -> Vec<&HistoryEntry<E, EventDispatchResult>> {
+        self.history
+            .as_ref()
+            .map_or_else(Vec::new, |history| history.entries(category))
+    }
+
     /// Adds the given `Subscriber` to a prioritized subscriber list to receive published messages of the given event category
+    ///
+    /// ### Returns
+    /// - `Subscription`: a guard which unsubscribes the `Subscriber` once it's dropped, call `detach` on it to keep the `Subscriber` subscribed instead.
     pub fn subscribe<S: Subscriber<T, E> + 'static>(
         &mut self,
         subscriber: &Rc<S>,
         to_category: T,
         with_priority: P,
-    ) {
+    ) -> Subscription {
+        let subscriber = SubscriberRef::shared(Rc::downgrade(
+            &(subscriber.clone() as Rc<dyn Subscriber<T, E> + 'static>),
+        ));
+        let subscription = subscriber.subscription(&self.dropped);
+        self.add(subscriber, to_category, with_priority);
+        subscription
+    }
+
+    /// Adds the given `Subscriber` to a prioritized subscriber list to receive published messages of the given event category through `on_event_mut`
+    ///
+    /// ### Returns
+    /// - `Subscription`: a guard which unsubscribes the `Subscriber` once it's dropped, call `detach` on it to keep the `Subscriber` subscribed instead.
+    ///
+    /// ### Notes
+    /// - A `Subscriber` which is already borrowed when an event is dispatched to it (i.e. because it's the one that dispatched the event) will not receive the event.
+    pub fn subscribe_mut<S: Subscriber<T, E> + 'static>(
+        &mut self,
+        subscriber: &Rc<RefCell<S>>,
+        to_category: T,
+        with_priority: P,
+    ) -> Subscription {
+        let subscriber = SubscriberRef::mutable(Rc::downgrade(
+            &(subscriber.clone() as Rc<RefCell<dyn Subscriber<T, E> + 'static>>),
+        ));
+        let subscription = subscriber.subscription(&self.dropped);
+        self.add(subscriber, to_category, with_priority);
+        subscription
+    }
+
+    /// Adds the given `Subscriber` to a prioritized subscriber list to receive published messages of the given event category, but only those matching the given predicate
+    ///
+    /// ### Returns
+    /// - `Subscription`: a guard which unsubscribes the `Subscriber` once it's dropped, call `detach` on it to keep the `Subscriber` subscribed instead.
+    ///
+    /// ### Notes
+    /// - The predicate is checked before the `Subscriber` is handed each event, and an event it rejects is never delivered: it shows up in a `DispatchReport`'s `filtered` rather than its `received`, and doesn't count towards the dispatch result.
+    pub fn subscribe_filtered<S: Subscriber<T, E> + 'static, F: Fn(&E) -> bool + 'static>(
+        &mut self,
+        subscriber: &Rc<S>,
+        to_category: T,
+        with_priority: P,
+        predicate: F,
+    ) -> Subscription {
+        let subscriber = SubscriberRef::shared(Rc::downgrade(
+            &(subscriber.clone() as Rc<dyn Subscriber<T, E> + 'static>),
+        ))
+        .filtered_by(predicate);
+        let subscription = subscriber.subscription(&self.dropped);
+        self.add(subscriber, to_category, with_priority);
+        subscription
+    }
+
+    /// Adds the given `Subscriber` to the prioritized subscriber lists of each of the given event categories in the given priority segment at once
+    ///
+    /// ### Returns
+    /// - `Subscription`: a single guard for every one of those subscriptions, which unsubscribes the `Subscriber` from all of them once it's dropped. They all share its id, so `unsubscribe` still unsubscribes the `Subscriber` from one category at a time.
+    pub fn subscribe_many<S: Subscriber<T, E> + 'static, I: IntoIterator<Item = T>>(
+        &mut self,
+        subscriber: &Rc<S>,
+        to_categories: I,
+        with_priority: P,
+    ) -> Subscription
+    where
+        P: Clone,
+    {
+        let subscriber = SubscriberRef::shared(Rc::downgrade(
+            &(subscriber.clone() as Rc<dyn Subscriber<T, E> + 'static>),
+        ));
+        let subscription = subscriber.subscription(&self.dropped);
+        for to_category in to_categories {
+            self.add(subscriber.clone(), to_category, with_priority.clone());
+        }
+        subscription
+    }
+
+    /// Adds the given `Subscriber` to receive every event published on this `PriorityEventBus`, whatever its category
+    ///
+    /// ### Notes
+    /// - It's notified after every priority segment of the event's category, even if one of those halted propagation. Halting propagation itself only keeps the event from the rest of the `Subscriber`s subscribed to every category.
+    /// - Unlike any other `Subscriber`, it isn't subscribed to a category it can be unsubscribed from, so it stays subscribed until its `Subscription` is dropped, `unsubscribe_everywhere` is called for it, or it's dropped.
+    ///
+    /// ### Returns
+    /// - `Subscription`: a guard which unsubscribes the `Subscriber` once it's dropped, call `detach` on it to keep the `Subscriber` subscribed instead.
+    pub fn subscribe_all<S: Subscriber<T, E> + 'static>(
+        &mut self,
+        subscriber: &Rc<S>,
+    ) -> Subscription {
+        self.remove_dropped();
+        let subscriber = SubscriberRef::shared(Rc::downgrade(
+            &(subscriber.clone() as Rc<dyn Subscriber<T, E> + 'static>),
+        ));
+        let subscription = subscriber.subscription(&self.dropped);
+        self.catch_all.push(subscriber);
+        subscription
+    }
+
+    /// Subscribes the given closure to a prioritized subscriber list to receive published messages of the given event category, with this `PriorityEventBus` taking ownership of it
+    ///
+    /// ### Returns
+    /// - `FnHandle<(T, P)>`: a handle which can be handed to `unsubscribe_fn` to unsubscribe the closure again.
+    pub fn subscribe_fn<F: Fn(&E) -> BusRequest + 'static>(
+        &mut self,
+        to_category: T,
+        with_priority: P,
+        function: F,
+    ) -> FnHandle<(T, P)>
+    where
+        P: Clone,
+    {
+        let subscriber = SubscriberRef::owned(Rc::new(FnSubscriber::new(function)));
+        let handle = FnHandle {
+            id: subscriber.id(),
+            subscribed_to: (to_category.clone(), with_priority.clone()),
+        };
+        self.add(subscriber, to_category, with_priority);
+        handle
+    }
+
+    /// Adds the given `Subscriber` to a prioritized subscriber list to receive published messages of the given event category, handing it the latest `last` events dispatched to that category first, oldest first (see `HistoryEntry`)
+    ///
+    /// ### Returns
+    /// - `Subscription`: a guard which unsubscribes the `Subscriber` once it's dropped, call `detach` on it to keep the `Subscriber` subscribed instead.
+    pub fn subscribe_with_replay<S: Subscriber<T, E> + 'static>(
+        &mut self,
+        subscriber: &Rc<S>,
+        to_category: T,
+        with_priority: P,
+        last: usize,
+    ) -> Subscription {
+        let subscriber = SubscriberRef::shared(Rc::downgrade(
+            &(subscriber.clone() as Rc<dyn Subscriber<T, E> + 'static>),
+        ));
+        let subscription = subscriber.subscription(&self.dropped);
+        let events = self.history.as_ref().map_or_else(Vec::new, |history| {
+            history.latest(|category| *category == to_category, last)
+        });
+        if subscriber.replay(&events, self.isolation.as_mut()) {
+            self.add(subscriber, to_category, with_priority);
+        }
+        subscription
+    }
+
+    fn add(&mut self, subscriber: SubscriberRef<T, E>, to_category: T, with_priority: P) {
+        self.remove_dropped();
         if let Some(category_priority_map) = self.channels.get_mut(&to_category) {
             if let Some(subscriber_list) = category_priority_map.get_mut(&with_priority) {
                 // We have an existing subscriber list for this category, push a new subscriber to it
-                subscriber_list.push(Rc::downgrade(
-                    &(subscriber.clone() as Rc<dyn Subscriber<T, E> + 'static>),
-                ));
+                subscriber_list.push(subscriber);
             } else {
                 // No subscriber list exists yet for this priority segment in this category, create one
-                category_priority_map.insert(
-                    with_priority,
-                    vec![Rc::downgrade(
-                        &(subscriber.clone() as Rc<dyn Subscriber<T, E> + 'static>),
-                    )],
-                );
+                category_priority_map.insert(with_priority, vec![subscriber]);
             }
         } else {
             // This category doesn't exist yet, create it
             let mut priority_map = BTreeMap::default();
-            priority_map.insert(
-                with_priority,
-                vec![Rc::downgrade(
-                    &(subscriber.clone() as Rc<dyn Subscriber<T, E> + 'static>),
-                )],
-            );
+            priority_map.insert(with_priority, vec![subscriber]);
             self.channels.insert(to_category, priority_map);
         }
     }
 
-    /// Unsubscribes the given `Subscriber` from the given priority segment in the given category from this `PriorityEventBus`
+    /// Removes the entries of every `Subscription` dropped since we last did, so they're gone before we next touch our subscriber lists
+    fn remove_dropped(&mut self) {
+        let dropped = self.dropped.take();
+        if dropped.is_empty() {
+            return;
+        }
+        for category_priority_map in self.channels.values_mut() {
+            for subscriber_list in category_priority_map.values_mut() {
+                subscriber_list.retain(|subscriber| !dropped.contains(&subscriber.id()));
+            }
+        }
+        self.catch_all
+            .retain(|subscriber| !dropped.contains(&subscriber.id()));
+    }
+
+    /// Unsubscribes the `Subscriber` with the given id (see `Subscription::id`) from the given priority segment in the given category from this `PriorityEventBus`
     ///
     /// ### Notes
     /// - This method drills down to the provided category and priority segment within that category directly to locate and unsubscribe a `Subscriber`.
     /// - This method automatically removes any dropped subscribers it encounters during the search.
     ///
     /// ### Returns
-    /// - `bool`: `true` if the subscriber was successfully unsubscribed, `false` if it was not (for various reasons, including not found).
-    pub fn unsubscribe<S: Subscriber<T, E> + 'static>(
+    /// - `UnsubscribeOutcome`: whether the `Subscriber` was removed, wasn't subscribed to the given priority segment, or had already been dropped.
+    pub fn unsubscribe(
         &mut self,
-        subscriber: &S,
+        id: SubscriberId,
         from_category: &T,
         with_priority: &P,
-    ) -> bool {
-        // Grab our priority map
-        if let Some(category_priority_map) = self.channels.get_mut(from_category) {
-            // Grab the subscriber list and find the index of the subscriber to unsubscribe
-            if let Some(subscriber_list) = category_priority_map.get_mut(with_priority) {
-                let mut cleanup_required = false;
-                if let Some(idx) = subscriber_list.iter().position(|weak_sub| {
-                    if let Some(sub) = weak_sub.upgrade() {
-                        sub.id() == subscriber.id()
-                    } else {
-                        // Found an invalid reference to a subscriber (which was probably dropped by the owner)
-                        cleanup_required = true;
-                        false
-                    }
-                }) {
-                    // We can swap_remove here because the subscribers are pooled by priority, and the ordering of two subscribers with the same priority doesn't matter
-                    subscriber_list.swap_remove(idx);
-                    return true;
-                }
-                if cleanup_required {
-                    subscriber_list
-                        .retain(|susbcriber| Weak::clone(susbcriber).upgrade().is_some());
-                }
-            }
-        }
-        false
+    ) -> UnsubscribeOutcome {
+        self.remove_dropped();
+        // Grab our priority map, then the subscriber list to unsubscribe from
+        self.channels
+            .get_mut(from_category)
+            .and_then(|category_priority_map| category_priority_map.get_mut(with_priority))
+            .map_or(UnsubscribeOutcome::NotFound, |subscriber_list| {
+                unsubscribe_from_segment(subscriber_list, id)
+            })
     }
 
-    /// Removes all `Subscriber`s from this `PriorityEventBus`
+    /// Unsubscribes a closure subscribed through `subscribe_fn` from this `PriorityEventBus`, dropping it (see `unsubscribe`)
+    pub fn unsubscribe_fn(&mut self, handle: &FnHandle<(T, P)>) -> UnsubscribeOutcome {
+        let (from_category, with_priority) = &handle.subscribed_to;
+        self.unsubscribe(handle.id, from_category, with_priority)
+    }
+
+    /// Unsubscribes the given `Subscriber` from every category on this `PriorityEventBus` it's subscribed to (including all of them at once, see `subscribe_all`), however many times it's subscribed to each
+    ///
+    /// ### Notes
+    /// - `subscriber` is the `Rc` the `Subscriber` was subscribed through, i.e. an `Rc<RefCell<S>>` for one subscribed through `subscribe_mut`.
+    ///
+    /// ### Returns
+    /// - `usize`: the number of subscriptions which were removed.
+    pub fn unsubscribe_everywhere<S: ?Sized>(&mut self, subscriber: &Rc<S>) -> usize {
+        self.remove_dropped();
+        let mut removed = 0;
+        for category_priority_map in self.channels.values_mut() {
+            for subscriber_list in category_priority_map.values_mut() {
+                let before = subscriber_list.len();
+                subscriber_list.retain(|entry| !entry.refers_to(subscriber));
+                removed += before - subscriber_list.len();
+            }
+        }
+        let before = self.catch_all.len();
+        self.catch_all.retain(|entry| !entry.refers_to(subscriber));
+        removed += before - self.catch_all.len();
+        removed
+    }
+
+    /// Unsubscribes the `Subscriber` with the given id (see `Subscription::id`) from the given category on this `PriorityEventBus`, whichever priority segment it's in
+    ///
+    /// ### Notes
+    /// - This method automatically removes any dropped subscribers it encounters during the search.
+    ///
+    /// ### Returns
+    /// - `UnsubscribeOutcome`: whether the `Subscriber` was removed, wasn't subscribed to the given category, or had already been dropped.
+    pub fn unsubscribe_from_category(
+        &mut self,
+        id: SubscriberId,
+        from_category: &T,
+    ) -> UnsubscribeOutcome {
+        self.remove_dropped();
+        if let Some(category_priority_map) = self.channels.get_mut(from_category) {
+            for subscriber_list in category_priority_map.values_mut() {
+                match unsubscribe_from_segment(subscriber_list, id) {
+                    UnsubscribeOutcome::NotFound => continue,
+                    outcome => return outcome,
+                }
+            }
+        }
+        UnsubscribeOutcome::NotFound
+    }
+
+    /// Removes all `Subscriber`s from this `PriorityEventBus`
     ///
     /// ### Notes
     /// - The memory previously allocated for the `Subscriber`s remains allocated for reuse.
     pub fn unsubscribe_all(&mut self) {
-        self.channels.clear()
+        self.remove_dropped();
+        self.channels.clear();
+        self.catch_all.clear();
     }
 
     /// Removes all `Subscriber`s from the given category on this `PriorityEventBus`
     pub fn unsubscribe_all_from_category(&mut self, from_category: &T) {
+        self.remove_dropped();
         self.channels.remove(from_category);
     }
 
@@ -258,6 +992,7 @@ where
         from_category: &T,
         with_priority: &P,
     ) {
+        self.remove_dropped();
         if let Some(category_priority_map) = self.channels.get_mut(from_category) {
             category_priority_map.remove(with_priority);
         }
@@ -267,33 +1002,1486 @@ where
     ///
     /// ### Notes
     /// - Automatically removes any dropped `Subscriber`s in the channel the given event belongs to, if the bus encounters any.
+    /// - Priority segments are dispatched to in ascending order of `P`, and a `Subscriber` requesting `DoNotPropagate` halts dispatch to every segment after its own.
     pub fn dispatch_event(&mut self, event: &E) -> EventDispatchResult {
-        let mut result = EventDispatchResult::NotNeeded;
-        // Grab the priority map for our category
-        if let Some(category_priority_map) = self.channels.get_mut(&event.category()) {
-            // For each distinct priority segment, in order of priority
-            for subscriber_list in category_priority_map.values_mut() {
-                // Dispatch the event, automatically stopping propagation via `execute_bus_requests` if necessary
-                let mut cleanup_required = false;
-                result = execute_bus_requests(subscriber_list, |weak_subscriber| {
-                    if let Some(subscriber) = weak_subscriber.upgrade() {
-                        subscriber.on_event(event)
-                    } else {
-                        // Found an invalid reference to a subscriber (which was probably dropped by the owner)
-                        cleanup_required = true;
-                        BusRequest::NoActionNeeded
+        let result = self.dispatch(event, None, |_| ());
+        self.run_deferred();
+        result.into()
+    }
+
+    /// Dispatches the given event to all `Subscriber`s of that event's category, as `dispatch_event` does, telling which priority segment halted propagation (if any did) along with the failures summed across every segment
+    pub fn dispatch_event_with_stop(&mut self, event: &E) -> PriorityDispatchResult<P>
+    where
+        P: Clone,
+    {
+        let result = self.dispatch(event, None, P::clone);
+        self.run_deferred();
+        result
+    }
+
+    /// Dispatches the given event to all `Subscriber`s of that event's category, noting down what became of each of them along the way
+    ///
+    /// ### Notes
+    /// - Automatically removes any dropped `Subscriber`s in the channel the given event belongs to, if the bus encounters any.
+    /// - Priority segments are dispatched to in ascending order of `P`, and a `Subscriber` requesting `DoNotPropagate` halts dispatch to every segment after its own.
+    pub fn dispatch_event_with_report(
+        &mut self,
+        event: &E,
+    ) -> (EventDispatchResult, DispatchReport) {
+        let mut report = DispatchReport::default();
+        let result = self.dispatch(event, Some(&mut report), |_| ());
+        self.run_deferred();
+        (result.into(), report)
+    }
+
+    /// A handle onto this bus's queue of deferred commands, through which `Subscriber`s can publish events and change subscriptions from inside `on_event`
+    ///
+    /// ### Notes
+    /// - Commands queued while dispatching an event are run once this bus has finished dispatching it, in the order they were queued in.
+    pub fn dispatch_queue(&self) -> DispatchQueue<Self, E> {
+        self.deferred.clone()
+    }
+
+    /// Runs every command deferred by our subscribers, including any deferred along the way, until there are none left
+    fn run_deferred(&mut self) {
+        while let Some(command) = self.deferred.pop() {
+            match command {
+                Command::Publish(event) => {
+                    self.dispatch(&event, None, |_| ());
+                }
+                Command::Apply(command) => command(self),
+            }
+        }
+    }
+
+    /// Dispatches the given event through `dispatch_to_subscribers`, handing it to our dead-letter sink afterwards if it didn't reach a single subscriber, and recording it in our history
+    ///
+    /// `stop` notes down the priority segment which halted propagation, if any did
+    fn dispatch<S: Clone>(
+        &mut self,
+        event: &E,
+        report: Option<&mut DispatchReport>,
+        stop: fn(&P) -> S,
+    ) -> PriorityDispatchResult<S> {
+        self.remove_dropped();
+        let result = if self.dead_letters.is_none() {
+            self.dispatch_to_subscribers(event, report, stop)
+        } else {
+            // Whether the event is a dead letter is told by what became of it, so we need a report of our own if we weren't asked for one
+            let mut own_report = DispatchReport::default();
+            let report = report.unwrap_or(&mut own_report);
+            let result = self.dispatch_to_subscribers(event, Some(&mut *report), stop);
+            if let (Some(sink), Some(reason)) = (&self.dead_letters, report.dead_letter()) {
+                sink(event, reason);
+            }
+            result
+        };
+        if let Some(history) = self.history.as_mut() {
+            let flattened = EventDispatchResult::from(result.clone());
+            history.record(route(&self.routing, event.categories()), event, &flattened);
+        }
+        result
+    }
+
+    fn dispatch_to_subscribers<S>(
+        &mut self,
+        event: &E,
+        mut report: Option<&mut DispatchReport>,
+        stop: fn(&P) -> S,
+    ) -> PriorityDispatchResult<S> {
+        let mut dispatched = false;
+        let mut stopped_at = None;
+        let mut failures = 0;
+        let mut handed = Handed::default();
+        let mut isolation = self.isolation.as_mut();
+        // Grab the priority map for each category the event is routed to, if one exists
+        'route: for category in route(&self.routing, event.categories()) {
+            if let Some(category_priority_map) = self.channels.get_mut(&category) {
+                // For each distinct priority segment, in order of priority, until propagation is halted
+                for (priority, subscriber_list) in category_priority_map.iter_mut() {
+                    // Dispatch the event, automatically stopping propagation via `execute_bus_requests` if necessary
+                    let (stopped, segment_failures) = match execute_bus_requests(
+                        subscriber_list,
+                        report.as_deref_mut(),
+                        |subscriber| {
+                            handed.deliver(subscriber.address(), || {
+                                subscriber.deliver_isolated(event, isolation.as_deref_mut())
+                            })
+                        },
+                    ) {
+                        Some(outcome) => outcome,
+                        // Every subscriber in this segment filtered the event out
+                        None => continue,
+                    };
+                    dispatched = true;
+                    // Failures are summed across segments, so a later segment which finished cleanly can't hide those of an earlier one
+                    failures += segment_failures;
+                    if stopped {
+                        // Propagation was halted, lower priority segments (and later categories) must not see the event
+                        stopped_at = Some(stop(priority));
+                        // Whoever was handed the event in this category mustn't be handed it again by the catch-all pass below
+                        handed.next_category();
+                        break 'route;
                     }
-                });
-                if cleanup_required {
-                    subscriber_list
-                        .retain(|subscriber| Weak::clone(subscriber).upgrade().is_some());
                 }
+                handed.next_category();
+            }
+        }
+        // Subscribers of every category see the event even if propagation was halted before them
+        if !self.catch_all.is_empty() {
+            if let Some((_, catch_all_failures)) =
+                execute_bus_requests(&mut self.catch_all, report, |subscriber| {
+                    handed.deliver(subscriber.address(), || {
+                        subscriber.deliver_isolated(event, isolation.as_deref_mut())
+                    })
+                })
+            {
+                dispatched = true;
+                failures += catch_all_failures;
+            }
+        }
+        if dispatched {
+            priority_dispatch_result(stopped_at, failures)
+        } else {
+            PriorityDispatchResult::NotNeeded
+        }
+    }
+}
+
+/// Removes the `Subscriber` with the given id from the given priority segment, along with any dropped subscribers in it
+fn unsubscribe_from_segment<T, E>(
+    subscriber_list: &mut Vec<SubscriberRef<T, E>>,
+    id: SubscriberId,
+) -> UnsubscribeOutcome
+where
+    T: Eq + PartialEq + Hash + Clone + 'static,
+    E: Event<T> + Eq + PartialEq + Hash + Clone + 'static,
+{
+    let mut outcome = UnsubscribeOutcome::NotFound;
+    // Find the index of the subscriber to unsubscribe
+    if let Some(idx) = subscriber_list
+        .iter()
+        .position(|subscriber| subscriber.id() == id)
+    {
+        // Subscribers within a priority segment are notified in the order they subscribed in, so the rest of the segment must keep its order
+        outcome = if subscriber_list.remove(idx).is_dropped() {
+            UnsubscribeOutcome::AlreadyDead
+        } else {
+            UnsubscribeOutcome::Removed
+        };
+    }
+    if subscriber_list
+        .iter()
+        .any(|subscriber| subscriber.is_dropped())
+    {
+        // Found invalid references to subscribers (which were probably dropped by their owners)
+        subscriber_list.retain(|subscriber| !subscriber.is_dropped());
+    }
+    outcome
+}
+
+/// Single-thread datastructure responsible for dispatching events from `Publisher`s to `Subscriber`s by topic
+///
+/// This keeps the respective Pub/Sub systems decoupled from each other
+///
+/// An event's category is a `/`-separated topic such as `sensors/kitchen/temperature`, which `Subscriber`s subscribe to through MQTT-style topic filters:
+/// - `+` matches any single level of a topic, i.e. `sensors/+/temperature`.
+/// - `#`, as a filter's last level, matches whatever is left of a topic (including nothing at all), i.e. `sensors/#`.
+///
+/// `Subscriber`s are notified filter by filter: at each level of the topic, `Subscriber`s of filters naming that level come first, followed by those of filters with a `+` there, followed by those of filters ending in a `#` there. `Subscriber`s of the same filter are notified in the order they subscribed in.
+///
+/// A `Subscriber` of several filters matching the same topic (i.e. `sensors/+` and `sensors/#`) is only handed each event once, through the first of them.
+///
+/// This should be wrapped in a Rc<RefCell<TopicEventBus>>
+pub struct TopicEventBus<E>
+where
+    E: Event<String> + Eq + PartialEq + Hash + Clone + 'static,
+{
+    // Subscriber lists are kept by topic filter, level by level, so a topic can be matched against every filter at once
+    channels: TopicTrie<SubscriberRef<String, E>>,
+    // How subscribers which panic are dealt with, if this bus was set up through `isolating_panics`
+    isolation: Option<Isolation>,
+    // Where events which don't reach a single subscriber are handed, if this bus was set up through `collecting_dead_letters`
+    dead_letters: Option<DeadLetterSink<E>>,
+    // The latest events dispatched to each topic, if this bus was set up through `recording_history`
+    history: Option<History<String, E, EventDispatchResult>>,
+    // The ids of `Subscription`s dropped since we last removed their entries (see `remove_dropped`)
+    dropped: DroppedSubscriptions,
+    // Commands deferred by subscribers until we've finished dispatching the current event
+    deferred: DispatchQueue<Self, E>,
+}
+
+impl<E> Default for TopicEventBus<E>
+where
+    E: Event<String> + Eq + PartialEq + Hash + Clone + 'static,
+{
+    fn default() -> Self {
+        Self {
+            channels: TopicTrie::default(),
+            isolation: None,
+            dead_letters: None,
+            history: None,
+            dropped: DroppedSubscriptions::default(),
+            deferred: DispatchQueue::default(),
+        }
+    }
+}
+
+impl<E> TopicEventBus<E>
+where
+    E: Event<String> + Eq + PartialEq + Hash + Clone + 'static,
+{
+    /// Has this `TopicEventBus` catch any panic a `Subscriber` throws while handling an event, rather than letting it unwind through the whole dispatch, and deal with that `Subscriber` as the given policy says (see `PanicPolicy`)
+    pub fn isolating_panics(mut self, policy: PanicPolicy) -> Self {
+        self.isolation = Some(Isolation::new(policy));
+        self
+    }
+
+    /// The id of every `Subscriber` quarantined after panicking (see `PanicPolicy::Quarantine`), in the order they were quarantined in
+    pub fn quarantined(&self) -> Vec<SubscriberId> {
+        self.isolation
+            .as_ref()
+            .map_or_else(Vec::new, Isolation::quarantined)
+    }
+
+    /// Lets the quarantined `Subscriber` with the given id be handed events again
+    ///
+    /// ### Returns
+    /// - `bool`: whether the `Subscriber` was quarantined at all.
+    pub fn release(&mut self, id: SubscriberId) -> bool {
+        self.isolation
+            .as_mut()
+            .map_or(false, |isolation| isolation.release(id))
+    }
+
+    /// Has this `TopicEventBus` hand every event which doesn't reach a single `Subscriber` to the given sink, along with why it didn't (see `DeadLetter`)
+    pub fn collecting_dead_letters<F: Fn(&E, DeadLetter) + 'static>(mut self, sink: F) -> Self {
+        self.dead_letters = Some(Box::new(sink));
+        self
+    }
+
+    /// Has this `TopicEventBus` keep the latest `capacity` events dispatched to each topic, along with what became of them (see `HistoryEntry`)
+    pub fn recording_history(mut self, capacity: usize) -> Self {
+        self.history = Some(History::new(capacity));
+        self
+    }
+
+    /// The latest events dispatched to the given topic, oldest first, if this `TopicEventBus` was set up through `recording_history`
+    pub fn history(&self, topic: &str) -> Vec<&HistoryEntry<E, EventDispatchResult>> {
+        self.history
+            .as_ref()
+            .map_or_else(Vec::new, |history| history.entries(topic))
+    }
+
+    /// Adds the given `Subscriber` to a subscriber list to receive published messages of every topic matching the given topic filter
+    ///
+    /// ### Returns
+    /// - `Ok(Subscription)`: a guard which unsubscribes the `Subscriber` once it's dropped, call `detach` on it to keep the `Subscriber` subscribed instead.
+    /// - `Err(TopicFilterError)`: the filter had a wildcard somewhere it isn't allowed, so the `Subscriber` wasn't subscribed.
+    pub fn subscribe<S: Subscriber<String, E> + 'static>(
+        &mut self,
+        subscriber: &Rc<S>,
+        to_filter: &str,
+    ) -> Result<Subscription, TopicFilterError> {
+        let subscriber = SubscriberRef::shared(Rc::downgrade(
+            &(subscriber.clone() as Rc<dyn Subscriber<String, E> + 'static>),
+        ));
+        let subscription = subscriber.subscription(&self.dropped);
+        self.add(subscriber, to_filter)?;
+        Ok(subscription)
+    }
+
+    /// Adds the given `Subscriber` to a subscriber list to receive published messages of every topic matching the given topic filter through `on_event_mut`
+    ///
+    /// ### Returns
+    /// - `Ok(Subscription)`: a guard which unsubscribes the `Subscriber` once it's dropped, call `detach` on it to keep the `Subscriber` subscribed instead.
+    /// - `Err(TopicFilterError)`: the filter had a wildcard somewhere it isn't allowed, so the `Subscriber` wasn't subscribed.
+    ///
+    /// ### Notes
+    /// - A `Subscriber` which is already borrowed when an event is dispatched to it (i.e. because it's the one that dispatched the event) will not receive the event.
+    pub fn subscribe_mut<S: Subscriber<String, E> + 'static>(
+        &mut self,
+        subscriber: &Rc<RefCell<S>>,
+        to_filter: &str,
+    ) -> Result<Subscription, TopicFilterError> {
+        let subscriber = SubscriberRef::mutable(Rc::downgrade(
+            &(subscriber.clone() as Rc<RefCell<dyn Subscriber<String, E> + 'static>>),
+        ));
+        let subscription = subscriber.subscription(&self.dropped);
+        self.add(subscriber, to_filter)?;
+        Ok(subscription)
+    }
+
+    /// Adds the given `Subscriber` to a subscriber list to receive published messages of every topic matching the given topic filter, but only those matching the given predicate
+    ///
+    /// ### Returns
+    /// - `Ok(Subscription)`: a guard which unsubscribes the `Subscriber` once it's dropped, call `detach` on it to keep the `Subscriber` subscribed instead.
+    /// - `Err(TopicFilterError)`: the filter had a wildcard somewhere it isn't allowed, so the `Subscriber` wasn't subscribed.
+    ///
+    /// ### Notes
+    /// - The predicate is checked before the `Subscriber` is handed each event, and an event it rejects is never delivered: it shows up in a `DispatchReport`'s `filtered` rather than its `received`, and doesn't count towards the dispatch result.
+    pub fn subscribe_filtered<S: Subscriber<String, E> + 'static, F: Fn(&E) -> bool + 'static>(
+        &mut self,
+        subscriber: &Rc<S>,
+        to_filter: &str,
+        predicate: F,
+    ) -> Result<Subscription, TopicFilterError> {
+        let subscriber = SubscriberRef::shared(Rc::downgrade(
+            &(subscriber.clone() as Rc<dyn Subscriber<String, E> + 'static>),
+        ))
+        .filtered_by(predicate);
+        let subscription = subscriber.subscription(&self.dropped);
+        self.add(subscriber, to_filter)?;
+        Ok(subscription)
+    }
+
+    /// Subscribes the given closure to receive published messages of every topic matching the given topic filter, with this `TopicEventBus` taking ownership of it
+    ///
+    /// ### Returns
+    /// - `Ok(FnHandle<String>)`: a handle which can be handed to `unsubscribe_fn` to unsubscribe the closure again.
+    /// - `Err(TopicFilterError)`: the filter had a wildcard somewhere it isn't allowed, so the closure wasn't subscribed.
+    pub fn subscribe_fn<F: Fn(&E) -> BusRequest + 'static>(
+        &mut self,
+        to_filter: &str,
+        function: F,
+    ) -> Result<FnHandle<String>, TopicFilterError> {
+        let subscriber = SubscriberRef::owned(Rc::new(FnSubscriber::new(function)));
+        let handle = FnHandle {
+            id: subscriber.id(),
+            subscribed_to: to_filter.to_string(),
+        };
+        self.add(subscriber, to_filter)?;
+        Ok(handle)
+    }
+
+    /// Adds the given `Subscriber` to a subscriber list to receive published messages of every topic matching the given topic filter, handing it the latest `last` events dispatched to any of those topics first, oldest first (see `HistoryEntry`)
+    ///
+    /// ### Returns
+    /// - `Ok(Subscription)`: a guard which unsubscribes the `Subscriber` once it's dropped, call `detach` on it to keep the `Subscriber` subscribed instead.
+    /// - `Err(TopicFilterError)`: the filter had a wildcard somewhere it isn't allowed, so the `Subscriber` wasn't subscribed (nor handed any events).
+    pub fn subscribe_with_replay<S: Subscriber<String, E> + 'static>(
+        &mut self,
+        subscriber: &Rc<S>,
+        to_filter: &str,
+        last: usize,
+    ) -> Result<Subscription, TopicFilterError> {
+        let filter = parse_filter(to_filter)?;
+        let subscriber = SubscriberRef::shared(Rc::downgrade(
+            &(subscriber.clone() as Rc<dyn Subscriber<String, E> + 'static>),
+        ));
+        let subscription = subscriber.subscription(&self.dropped);
+        let events = self.history.as_ref().map_or_else(Vec::new, |history| {
+            history.latest(|topic| filter_matches(&filter, topic), last)
+        });
+        if subscriber.replay(&events, self.isolation.as_mut()) {
+            self.remove_dropped();
+            self.channels.list_mut(&filter).push(subscriber);
+        }
+        Ok(subscription)
+    }
+
+    fn add(
+        &mut self,
+        subscriber: SubscriberRef<String, E>,
+        to_filter: &str,
+    ) -> Result<(), TopicFilterError> {
+        self.remove_dropped();
+        let filter = parse_filter(to_filter)?;
+        self.channels.list_mut(&filter).push(subscriber);
+        Ok(())
+    }
+
+    /// Removes the entries of every `Subscription` dropped since we last did, so they're gone before we next touch our subscriber lists
+    fn remove_dropped(&mut self) {
+        let dropped = self.dropped.take();
+        if dropped.is_empty() {
+            return;
+        }
+        for subscriber_list in self.channels.lists_mut() {
+            subscriber_list.retain(|subscriber| !dropped.contains(&subscriber.id()));
+        }
+        self.channels.prune();
+    }
+
+    /// Unsubscribes the `Subscriber` with the given id (see `Subscription::id`) from the given topic filter on this `TopicEventBus`
+    ///
+    /// ### Notes
+    /// - `from_filter` has to be the filter the `Subscriber` subscribed to, not a topic it matches.
+    /// - Automatically removes any dropped subscribers of the given topic filter, if the bus encounters any.
+    ///
+    /// ### Returns
+    /// - `UnsubscribeOutcome`: whether the `Subscriber` was removed, wasn't subscribed to the given topic filter, or had already been dropped.
+    pub fn unsubscribe(&mut self, id: SubscriberId, from_filter: &str) -> UnsubscribeOutcome {
+        self.remove_dropped();
+        let mut outcome = UnsubscribeOutcome::NotFound;
+        let filter = match parse_filter(from_filter) {
+            Ok(filter) => filter,
+            Err(_) => return outcome,
+        };
+        if let Some(subscriber_list) = self.channels.find_mut(&filter) {
+            if let Some(idx) = subscriber_list
+                .iter()
+                .position(|subscriber| subscriber.id() == id)
+            {
+                // Subscribers are notified in the order they subscribed in, so the rest of the list must keep its order
+                outcome = if subscriber_list.remove(idx).is_dropped() {
+                    UnsubscribeOutcome::AlreadyDead
+                } else {
+                    UnsubscribeOutcome::Removed
+                };
+            }
+
+            if subscriber_list
+                .iter()
+                .any(|subscriber| subscriber.is_dropped())
+            {
+                subscriber_list.retain(|subscriber| !subscriber.is_dropped());
             }
         }
+        self.channels.prune();
+        outcome
+    }
+
+    /// Unsubscribes a closure subscribed through `subscribe_fn` from this `TopicEventBus`, dropping it (see `unsubscribe`)
+    pub fn unsubscribe_fn(&mut self, handle: &FnHandle<String>) -> UnsubscribeOutcome {
+        self.unsubscribe(handle.id, &handle.subscribed_to)
+    }
+
+    /// Unsubscribes the given `Subscriber` from every topic filter on this `TopicEventBus` it's subscribed to, however many times it's subscribed to each
+    ///
+    /// ### Notes
+    /// - `subscriber` is the `Rc` the `Subscriber` was subscribed through, i.e. an `Rc<RefCell<S>>` for one subscribed through `subscribe_mut`.
+    ///
+    /// ### Returns
+    /// - `usize`: the number of subscriptions which were removed.
+    pub fn unsubscribe_everywhere<S: ?Sized>(&mut self, subscriber: &Rc<S>) -> usize {
+        self.remove_dropped();
+        let mut removed = 0;
+        for subscriber_list in self.channels.lists_mut() {
+            let before = subscriber_list.len();
+            subscriber_list.retain(|entry| !entry.refers_to(subscriber));
+            removed += before - subscriber_list.len();
+        }
+        self.channels.prune();
+        removed
+    }
+
+    /// Removes all `Subscriber`s from this `TopicEventBus`
+    pub fn unsubscribe_all(&mut self) {
+        self.remove_dropped();
+        self.channels.clear();
+    }
+
+    /// Removes all `Subscriber`s from the given topic filter on this `TopicEventBus`
+    ///
+    /// ### Notes
+    /// - `from_filter` is a filter, not a topic, so `Subscriber`s of other filters matching the same topics stay subscribed.
+    pub fn unsubscribe_all_from_filter(&mut self, from_filter: &str) {
+        self.remove_dropped();
+        if let Ok(filter) = parse_filter(from_filter) {
+            if let Some(subscriber_list) = self.channels.find_mut(&filter) {
+                subscriber_list.clear();
+            }
+            self.channels.prune();
+        }
+    }
+
+    /// Dispatches the given event to all `Subscriber`s of every topic filter matching that event's topic
+    ///
+    /// ### Notes
+    /// - Automatically removes any dropped `Subscriber`s of the topic filters the given event's topic matches, if the bus encounters any.
+    /// - A `Subscriber` requesting `DoNotPropagate` halts dispatch to every topic filter after its own.
+    pub fn dispatch_event(&mut self, event: &E) -> EventDispatchResult {
+        let result = self.dispatch(event, None);
+        self.run_deferred();
+        result
+    }
+
+    /// Dispatches the given event to all `Subscriber`s of every topic filter matching that event's topic, noting down what became of each of them along the way
+    ///
+    /// ### Notes
+    /// - Automatically removes any dropped `Subscriber`s of the topic filters the given event's topic matches, if the bus encounters any.
+    /// - A `Subscriber` requesting `DoNotPropagate` halts dispatch to every topic filter after its own.
+    pub fn dispatch_event_with_report(
+        &mut self,
+        event: &E,
+    ) -> (EventDispatchResult, DispatchReport) {
+        let mut report = DispatchReport::default();
+        let result = self.dispatch(event, Some(&mut report));
+        self.run_deferred();
+        (result, report)
+    }
+
+    /// A handle onto this bus's queue of deferred commands, through which `Subscriber`s can publish events and change subscriptions from inside `on_event`
+    ///
+    /// ### Notes
+    /// - Commands queued while dispatching an event are run once this bus has finished dispatching it, in the order they were queued in.
+    pub fn dispatch_queue(&self) -> DispatchQueue<Self, E> {
+        self.deferred.clone()
+    }
+
+    /// Runs every command deferred by our subscribers, including any deferred along the way, until there are none left
+    fn run_deferred(&mut self) {
+        while let Some(command) = self.deferred.pop() {
+            match command {
+                Command::Publish(event) => {
+                    self.dispatch(&event, None);
+                }
+                Command::Apply(command) => command(self),
+            }
+        }
+    }
+
+    /// Dispatches the given event through `dispatch_to_subscribers`, handing it to our dead-letter sink afterwards if it didn't reach a single subscriber, and recording it in our history
+    fn dispatch(&mut self, event: &E, report: Option<&mut DispatchReport>) -> EventDispatchResult {
+        self.remove_dropped();
+        let result = if self.dead_letters.is_none() {
+            self.dispatch_to_subscribers(event, report)
+        } else {
+            // Whether the event is a dead letter is told by what became of it, so we need a report of our own if we weren't asked for one
+            let mut own_report = DispatchReport::default();
+            let report = report.unwrap_or(&mut own_report);
+            let result = self.dispatch_to_subscribers(event, Some(&mut *report));
+            if let (Some(sink), Some(reason)) = (&self.dead_letters, report.dead_letter()) {
+                sink(event, reason);
+            }
+            result
+        };
+        if let Some(history) = self.history.as_mut() {
+            history.record(distinct(event.categories()), event, &result);
+        }
         result
     }
+
+    fn dispatch_to_subscribers(
+        &mut self,
+        event: &E,
+        mut report: Option<&mut DispatchReport>,
+    ) -> EventDispatchResult {
+        let mut handed = Handed::default();
+        let mut isolation = self.isolation.as_mut();
+        let mut outcome = None;
+        let mut emptied = false;
+        // Have the subscribers of every filter matching each of the event's topics handle the dispatched event in turn, until propagation is halted
+        for topic in distinct(event.categories()) {
+            let levels: Vec<&str> = topic.split('/').collect();
+            let stopped = self
+                .channels
+                .visit_matching(&levels, &mut |subscriber_list| {
+                    let requests = execute_bus_requests(
+                        subscriber_list,
+                        report.as_deref_mut(),
+                        |subscriber| {
+                            handed.deliver(subscriber.address(), || {
+                                subscriber.deliver_isolated(event, isolation.as_deref_mut())
+                            })
+                        },
+                    );
+                    emptied |= subscriber_list.is_empty();
+                    // Each filter counts as a category of its own, so a subscriber of overlapping filters is only handed the event once
+                    handed.next_category();
+                    match requests {
+                        Some((stopped, failures)) => {
+                            let (_, earlier_failures) = outcome.unwrap_or((false, 0));
+                            outcome = Some((stopped, earlier_failures + failures));
+                            stopped
+                        }
+                        None => false,
+                    }
+                });
+            if stopped {
+                break;
+            }
+        }
+        // Subscribers removed along the way may have left levels of the trie which no longer lead to any
+        if emptied {
+            self.channels.prune();
+        }
+        match outcome {
+            Some((stopped, failures)) => dispatch_result(stopped, failures),
+            None => EventDispatchResult::NotNeeded,
+        }
+    }
 }
 
-// TODO: Add ParallelEventBus
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::cell::{Cell, RefCell};
+
+    #[derive(Debug, Eq, PartialEq, Hash, Clone)]
+    enum Category {
+        Input,
+        Keyboard,
+        Window,
+    }
+
+    #[derive(Debug, Eq, PartialEq, Hash, Clone)]
+    struct TestEvent(Category, u32);
+
+    impl Event<Category> for TestEvent {
+        fn category(&self) -> Category {
+            self.0.clone()
+        }
+    }
+
+    impl HierarchicalCategory for Category {
+        fn parent(&self) -> Option<Self> {
+            match self {
+                Category::Keyboard => Some(Category::Input),
+                _ => None,
+            }
+        }
+    }
+
+    /// Notes down every event it's handed, answering each with the same request
+    struct Recorder {
+        received: RefCell<Vec<u32>>,
+        request: BusRequest,
+    }
+
+    impl Recorder {
+        fn new(request: BusRequest) -> Rc<Self> {
+            Rc::new(Self {
+                received: RefCell::new(Vec::new()),
+                request,
+            })
+        }
+    }
+
+    fn received(subscriber: &Recorder) -> Vec<u32> {
+        subscriber.received.borrow().clone()
+    }
+
+    impl Subscriber<Category, TestEvent> for Recorder {
+        fn on_event(&self, event: &TestEvent) -> BusRequest {
+            self.received.borrow_mut().push(event.1);
+            self.request.clone()
+        }
+    }
+
+    /// An event which belongs to both the `Input` and `Window` categories
+    #[derive(Debug, Eq, PartialEq, Hash, Clone)]
+    struct SharedEvent(u32);
+
+    impl Event<Category> for SharedEvent {
+        fn category(&self) -> Category {
+            Category::Input
+        }
+
+        fn categories(&self) -> Vec<Category> {
+            vec![Category::Input, Category::Window]
+        }
+    }
+
+    impl Subscriber<Category, SharedEvent> for Recorder {
+        fn on_event(&self, event: &SharedEvent) -> BusRequest {
+            self.received.borrow_mut().push(event.0);
+            self.request.clone()
+        }
+    }
 
-// TODO: Add testing
+    #[derive(Debug, Eq, PartialEq, Hash, Clone)]
+    struct TopicEvent(&'static str, u32);
+
+    impl Event<String> for TopicEvent {
+        fn category(&self) -> String {
+            self.0.to_string()
+        }
+    }
+
+    impl Subscriber<String, TopicEvent> for Recorder {
+        fn on_event(&self, event: &TopicEvent) -> BusRequest {
+            self.received.borrow_mut().push(event.1);
+            self.request.clone()
+        }
+    }
+
+    #[test]
+    fn do_not_propagate_halts_every_lower_priority_segment() {
+        let mut bus = PriorityEventBus::<Category, TestEvent, u8>::default();
+        let first = Recorder::new(BusRequest::DoNotPropagate);
+        let later = Recorder::new(BusRequest::NoActionNeeded);
+        bus.subscribe(&first, Category::Input, 1).detach();
+        bus.subscribe(&later, Category::Input, 2).detach();
+        assert_eq!(
+            bus.dispatch_event(&TestEvent(Category::Input, 1)),
+            EventDispatchResult::Stopped
+        );
+        assert_eq!(received(&first), vec![1]);
+        assert!(received(&later).is_empty());
+        assert_eq!(
+            bus.dispatch_event(&TestEvent(Category::Window, 2)),
+            EventDispatchResult::NotNeeded
+        );
+    }
+
+    #[test]
+    fn clean_priority_segment_keeps_the_failures_of_an_earlier_one() {
+        let mut bus = PriorityEventBus::<Category, TestEvent, u8>::default();
+        let failing = Recorder::new(BusRequest::DispatchFailed);
+        let later = Recorder::new(BusRequest::NoActionNeeded);
+        bus.subscribe(&failing, Category::Input, 1).detach();
+        bus.subscribe(&later, Category::Input, 2).detach();
+        assert_eq!(
+            bus.dispatch_event(&TestEvent(Category::Input, 1)),
+            EventDispatchResult::FinishedWithFailures(1)
+        );
+        assert_eq!(received(&later), vec![1]);
+    }
+
+    #[test]
+    fn failures_are_summed_across_priority_segments_up_to_the_one_which_stopped() {
+        let mut bus = PriorityEventBus::<Category, TestEvent, u8>::default();
+        let failing = Recorder::new(BusRequest::DispatchFailed);
+        let also_failing = Recorder::new(BusRequest::DispatchFailed);
+        let halting = Recorder::new(BusRequest::DoNotPropagate);
+        let never_reached = Recorder::new(BusRequest::DispatchFailed);
+        bus.subscribe(&failing, Category::Input, 1).detach();
+        bus.subscribe(&also_failing, Category::Input, 2).detach();
+        bus.subscribe(&halting, Category::Input, 2).detach();
+        bus.subscribe(&never_reached, Category::Input, 3).detach();
+        let result = bus.dispatch_event_with_stop(&TestEvent(Category::Input, 1));
+        assert_eq!(result, PriorityDispatchResult::StoppedWithFailures(2, 2));
+        assert_eq!(result.stopped_at(), Some(&2));
+        assert_eq!(result.failures(), 2);
+        assert!(received(&never_reached).is_empty());
+    }
+
+    #[test]
+    fn report_names_who_received_unsubscribed_and_stopped() {
+        let mut bus = EventBus::default();
+        let staying = Recorder::new(BusRequest::NoActionNeeded);
+        let leaving = Recorder::new(BusRequest::Unsubscribe);
+        let halting = Recorder::new(BusRequest::DoNotPropagate);
+        let staying = bus.subscribe(&staying, Category::Input);
+        let leaving = bus.subscribe(&leaving, Category::Input);
+        let halting = bus.subscribe(&halting, Category::Input);
+        bus.subscribe(&Recorder::new(BusRequest::NoActionNeeded), Category::Window)
+            .detach();
+        let (result, report) = bus.dispatch_event_with_report(&TestEvent(Category::Input, 1));
+        assert_eq!(result, EventDispatchResult::Stopped);
+        assert_eq!(
+            report,
+            DispatchReport {
+                received: vec![staying.id(), leaving.id(), halting.id()],
+                filtered: vec![],
+                failed: vec![],
+                queued: vec![],
+                unsubscribed: vec![leaving.id()],
+                stopped_by: Some(halting.id()),
+                dropped: 0,
+            }
+        );
+        let (result, report) = bus.dispatch_event_with_report(&TestEvent(Category::Window, 2));
+        assert_eq!(result, EventDispatchResult::Finished);
+        assert!(report.received.is_empty());
+        assert_eq!(report.dropped, 1);
+    }
+
+    #[test]
+    fn subscribers_keep_their_order_as_others_leave() {
+        let mut bus = EventBus::default();
+        let leaving = Recorder::new(BusRequest::Unsubscribe);
+        let rest: Vec<_> = (0..3)
+            .map(|_| Recorder::new(BusRequest::NoActionNeeded))
+            .collect();
+        bus.subscribe(&leaving, Category::Input).detach();
+        let rest: Vec<_> = rest
+            .iter()
+            .map(|subscriber| bus.subscribe(subscriber, Category::Input))
+            .collect();
+        bus.dispatch_event(&TestEvent(Category::Input, 1));
+        let (_, report) = bus.dispatch_event_with_report(&TestEvent(Category::Input, 2));
+        assert_eq!(
+            report.received,
+            vec![rest[0].id(), rest[1].id(), rest[2].id()]
+        );
+        bus.unsubscribe(rest[0].id(), Category::Input);
+        let (_, report) = bus.dispatch_event_with_report(&TestEvent(Category::Input, 3));
+        assert_eq!(report.received, vec![rest[1].id(), rest[2].id()]);
+    }
+
+    /// Follows up on an `Input` event by handing `late` a `Window` event, then leaves
+    struct Relay {
+        id: Cell<Option<SubscriberId>>,
+        queue: DispatchQueue<EventBus<Category, TestEvent>, TestEvent>,
+        late: Rc<Recorder>,
+    }
+
+    impl Subscriber<Category, TestEvent> for Relay {
+        fn on_event(&self, event: &TestEvent) -> BusRequest {
+            self.queue.subscribe(&self.late, Category::Window);
+            self.queue.publish(TestEvent(Category::Window, event.1 + 1));
+            self.queue
+                .unsubscribe(self.id.get().unwrap(), Category::Input);
+            BusRequest::NoActionNeeded
+        }
+    }
+
+    #[test]
+    fn subscribers_can_publish_and_resubscribe_through_the_dispatch_queue() {
+        let mut bus = EventBus::default();
+        let late = Recorder::new(BusRequest::NoActionNeeded);
+        let relay = Rc::new(Relay {
+            id: Cell::new(None),
+            queue: bus.dispatch_queue(),
+            late: Rc::clone(&late),
+        });
+        let subscription = bus.subscribe(&relay, Category::Input);
+        relay.id.set(Some(subscription.id()));
+        subscription.detach();
+        assert_eq!(
+            bus.dispatch_event(&TestEvent(Category::Input, 1)),
+            EventDispatchResult::Finished
+        );
+        assert_eq!(received(&late), vec![2]);
+        assert_eq!(
+            bus.dispatch_event(&TestEvent(Category::Input, 3)),
+            EventDispatchResult::Finished
+        );
+        assert_eq!(received(&late), vec![2]);
+        assert!(bus.channels[&Category::Input].is_empty());
+    }
+
+    #[test]
+    fn queued_events_wait_to_be_pumped_in_fifo_order() {
+        let mut bus = EventBus::default();
+        let recorder = Recorder::new(BusRequest::NoActionNeeded);
+        bus.subscribe(&recorder, Category::Input).detach();
+        for n in 1..=3 {
+            bus.queue_event(TestEvent(Category::Input, n));
+        }
+        bus.queue_event(TestEvent(Category::Window, 4));
+        assert!(received(&recorder).is_empty());
+        assert_eq!(
+            bus.pump(2),
+            vec![
+                (TestEvent(Category::Input, 1), EventDispatchResult::Finished),
+                (TestEvent(Category::Input, 2), EventDispatchResult::Finished)
+            ]
+        );
+        assert_eq!(bus.queued_events(), 2);
+        assert_eq!(
+            bus.drain(),
+            vec![
+                (TestEvent(Category::Input, 3), EventDispatchResult::Finished),
+                (
+                    TestEvent(Category::Window, 4),
+                    EventDispatchResult::NotNeeded
+                )
+            ]
+        );
+        assert_eq!(received(&recorder), vec![1, 2, 3]);
+        assert!(bus.drain().is_empty());
+    }
+
+    /// Counts the events it's handed without any interior mutability
+    struct Counter {
+        count: u32,
+    }
+
+    impl Subscriber<Category, TestEvent> for Counter {
+        fn on_event(&self, _: &TestEvent) -> BusRequest {
+            BusRequest::DispatchFailed
+        }
+
+        fn on_event_mut(&mut self, _: &TestEvent) -> BusRequest {
+            self.count += 1;
+            BusRequest::NoActionNeeded
+        }
+    }
+
+    #[test]
+    fn mutable_subscribers_are_handed_events_through_on_event_mut() {
+        let mut bus = PriorityEventBus::<Category, TestEvent, u8>::default();
+        let counter = Rc::new(RefCell::new(Counter { count: 0 }));
+        let subscription = bus.subscribe_mut(&counter, Category::Input, 1);
+        bus.dispatch_event(&TestEvent(Category::Input, 1));
+        assert_eq!(
+            bus.dispatch_event(&TestEvent(Category::Input, 2)),
+            EventDispatchResult::Finished
+        );
+        assert_eq!(counter.borrow().count, 2);
+        let borrowed = counter.borrow_mut();
+        assert_eq!(
+            bus.dispatch_event(&TestEvent(Category::Input, 3)),
+            EventDispatchResult::FinishedWithFailures(1)
+        );
+        drop(borrowed);
+        assert_eq!(
+            bus.unsubscribe(subscription.id(), &Category::Input, &1),
+            UnsubscribeOutcome::Removed
+        );
+        subscription.detach();
+        assert!(bus.channels[&Category::Input][&1].is_empty());
+    }
+
+    #[test]
+    fn closures_subscribe_without_implementing_subscriber() {
+        let mut bus = EventBus::default();
+        let seen = Rc::new(RefCell::new(Vec::new()));
+        let handle = {
+            let seen = Rc::clone(&seen);
+            bus.subscribe_fn(Category::Input, move |event: &TestEvent| {
+                seen.borrow_mut().push(event.1);
+                BusRequest::NoActionNeeded
+            })
+        };
+        bus.dispatch_event(&TestEvent(Category::Input, 1));
+        let (_, report) = bus.dispatch_event_with_report(&TestEvent(Category::Input, 2));
+        assert_eq!(report.received, vec![handle.id()]);
+        bus.unsubscribe_fn(&handle);
+        assert_eq!(
+            bus.dispatch_event(&TestEvent(Category::Input, 3)),
+            EventDispatchResult::Finished
+        );
+        assert_eq!(*seen.borrow(), vec![1, 2]);
+        assert_eq!(Rc::strong_count(&seen), 1);
+    }
+
+    #[test]
+    fn dropping_a_subscription_unsubscribes_right_away() {
+        let mut bus = EventBus::default();
+        let guarded = Recorder::new(BusRequest::NoActionNeeded);
+        let detached = Recorder::new(BusRequest::NoActionNeeded);
+        let subscription = bus.subscribe(&guarded, Category::Input);
+        bus.subscribe(&detached, Category::Input).detach();
+        bus.dispatch_event(&TestEvent(Category::Input, 1));
+        drop(subscription);
+        bus.dispatch_event(&TestEvent(Category::Input, 2));
+        assert_eq!(received(&guarded), vec![1]);
+        assert_eq!(received(&detached), vec![1, 2]);
+        assert_eq!(bus.channels[&Category::Input].len(), 1);
+    }
+
+    #[test]
+    fn every_subscription_gets_its_own_id() {
+        let mut bus = PriorityEventBus::<Category, TestEvent, u8>::default();
+        let recorder = Recorder::new(BusRequest::NoActionNeeded);
+        let first = bus.subscribe(&recorder, Category::Input, 1);
+        let second = bus.subscribe(&recorder, Category::Input, 1);
+        assert_ne!(first.id(), second.id());
+        assert_eq!(
+            bus.unsubscribe(first.id(), &Category::Input, &1),
+            UnsubscribeOutcome::Removed
+        );
+        assert_eq!(
+            bus.unsubscribe(first.id(), &Category::Input, &1),
+            UnsubscribeOutcome::NotFound
+        );
+        let (_, report) = bus.dispatch_event_with_report(&TestEvent(Category::Input, 1));
+        assert_eq!(report.received, vec![second.id()]);
+        assert_eq!(received(&recorder), vec![1]);
+    }
+
+    #[test]
+    fn subscribers_leave_every_category_and_priority_at_once() {
+        let mut bus = PriorityEventBus::<Category, TestEvent, u8>::default();
+        let leaving = Recorder::new(BusRequest::NoActionNeeded);
+        let staying = Recorder::new(BusRequest::NoActionNeeded);
+        bus.subscribe(&leaving, Category::Input, 1).detach();
+        bus.subscribe(&leaving, Category::Input, 2).detach();
+        bus.subscribe(&leaving, Category::Window, 1).detach();
+        let staying_input = bus.subscribe(&staying, Category::Input, 3);
+        bus.subscribe(&staying, Category::Window, 2).detach();
+        assert_eq!(bus.unsubscribe_everywhere(&leaving), 3);
+        assert_eq!(bus.unsubscribe_everywhere(&leaving), 0);
+        assert_eq!(
+            bus.unsubscribe_from_category(staying_input.id(), &Category::Input),
+            UnsubscribeOutcome::Removed
+        );
+        bus.dispatch_event(&TestEvent(Category::Input, 1));
+        bus.dispatch_event(&TestEvent(Category::Window, 2));
+        assert!(received(&leaving).is_empty());
+        assert_eq!(received(&staying), vec![2]);
+    }
+
+    #[test]
+    fn catch_all_subscribers_see_every_event_even_once_propagation_halts() {
+        let mut bus = EventBus::default();
+        let halting = Recorder::new(BusRequest::DoNotPropagate);
+        let many = Recorder::new(BusRequest::NoActionNeeded);
+        let everything = Recorder::new(BusRequest::NoActionNeeded);
+        bus.subscribe(&halting, Category::Input).detach();
+        let many_subscription = bus.subscribe_many(&many, vec![Category::Input, Category::Window]);
+        let everything_subscription = bus.subscribe_all(&everything);
+        assert_eq!(
+            bus.dispatch_event(&TestEvent(Category::Input, 1)),
+            EventDispatchResult::Stopped
+        );
+        bus.dispatch_event(&TestEvent(Category::Window, 2));
+        drop(many_subscription);
+        bus.dispatch_event(&TestEvent(Category::Window, 3));
+        assert_eq!(received(&many), vec![2]);
+        assert_eq!(received(&everything), vec![1, 2, 3]);
+        everything_subscription.detach();
+        assert_eq!(bus.unsubscribe_everywhere(&everything), 1);
+        bus.unsubscribe_all_from_category(Category::Window);
+        assert_eq!(
+            bus.dispatch_event(&TestEvent(Category::Window, 4)),
+            EventDispatchResult::NotNeeded
+        );
+    }
+
+    #[test]
+    fn hierarchical_buses_route_events_up_through_parent_categories() {
+        let mut bus = EventBus::hierarchical(RoutingOrder::MostSpecificFirst);
+        let input = Recorder::new(BusRequest::NoActionNeeded);
+        let keyboard = Recorder::new(BusRequest::DoNotPropagate);
+        bus.subscribe(&input, Category::Input).detach();
+        let keyboard_subscription = bus.subscribe(&keyboard, Category::Keyboard);
+        assert_eq!(
+            bus.dispatch_event(&TestEvent(Category::Keyboard, 1)),
+            EventDispatchResult::Stopped
+        );
+        drop(keyboard_subscription);
+        assert_eq!(
+            bus.dispatch_event(&TestEvent(Category::Keyboard, 2)),
+            EventDispatchResult::Finished
+        );
+        bus.dispatch_event(&TestEvent(Category::Input, 3));
+        assert_eq!(received(&input), vec![2, 3]);
+        assert_eq!(received(&keyboard), vec![1]);
+        let mut flat = EventBus::default();
+        flat.subscribe(&input, Category::Input).detach();
+        assert_eq!(
+            flat.dispatch_event(&TestEvent(Category::Keyboard, 4)),
+            EventDispatchResult::NotNeeded
+        );
+    }
+
+    #[test]
+    fn most_general_first_routing_dispatches_to_every_parent_segment_first() {
+        let mut bus = PriorityEventBus::<Category, TestEvent, u8>::hierarchical(
+            RoutingOrder::MostGeneralFirst,
+        );
+        let keyboard = Recorder::new(BusRequest::NoActionNeeded);
+        let input = Recorder::new(BusRequest::NoActionNeeded);
+        let halting = Recorder::new(BusRequest::DoNotPropagate);
+        bus.subscribe(&keyboard, Category::Keyboard, 0).detach();
+        bus.subscribe(&input, Category::Input, 1).detach();
+        let halting_subscription = bus.subscribe(&halting, Category::Input, 2);
+        assert_eq!(
+            bus.dispatch_event_with_stop(&TestEvent(Category::Keyboard, 1)),
+            PriorityDispatchResult::Stopped(2)
+        );
+        assert!(received(&keyboard).is_empty());
+        drop(halting_subscription);
+        assert_eq!(
+            bus.dispatch_event(&TestEvent(Category::Keyboard, 2)),
+            EventDispatchResult::Finished
+        );
+        assert_eq!(received(&input), vec![1, 2]);
+        assert_eq!(received(&keyboard), vec![2]);
+    }
+
+    #[test]
+    fn topic_filters_match_single_and_multi_level_wildcards() {
+        let mut bus = TopicEventBus::default();
+        let exact = Recorder::new(BusRequest::NoActionNeeded);
+        let single = Recorder::new(BusRequest::NoActionNeeded);
+        let multi = Recorder::new(BusRequest::NoActionNeeded);
+        let _exact = bus
+            .subscribe(&exact, "sensors/kitchen/temperature")
+            .unwrap();
+        let _single = bus.subscribe(&single, "sensors/+/temperature").unwrap();
+        let _multi = bus.subscribe(&multi, "sensors/#").unwrap();
+        bus.dispatch_event(&TopicEvent("sensors/kitchen/temperature", 1));
+        bus.dispatch_event(&TopicEvent("sensors/hall/temperature", 2));
+        bus.dispatch_event(&TopicEvent("sensors", 3));
+        assert_eq!(
+            bus.dispatch_event(&TopicEvent("sensors/hall/humidity/raw", 4)),
+            EventDispatchResult::Finished
+        );
+        assert_eq!(
+            bus.dispatch_event(&TopicEvent("ui/button", 5)),
+            EventDispatchResult::NotNeeded
+        );
+        assert_eq!(received(&exact), vec![1]);
+        assert_eq!(received(&single), vec![1, 2]);
+        assert_eq!(received(&multi), vec![1, 2, 3, 4]);
+        assert_eq!(
+            bus.subscribe(&exact, "sensors/#/temperature").err(),
+            Some(TopicFilterError::MisplacedMultiLevelWildcard)
+        );
+        assert_eq!(
+            bus.subscribe(&exact, "sensors/temp+").err(),
+            Some(TopicFilterError::PartialWildcard)
+        );
+    }
+
+    #[test]
+    fn more_literal_topic_filters_are_dispatched_to_first() {
+        let mut bus = TopicEventBus::default();
+        let multi = Recorder::new(BusRequest::NoActionNeeded);
+        let single = Recorder::new(BusRequest::DoNotPropagate);
+        let exact = Recorder::new(BusRequest::NoActionNeeded);
+        let _multi_subscription = bus.subscribe(&multi, "ui/#").unwrap();
+        let single_subscription = bus.subscribe(&single, "ui/+").unwrap();
+        bus.subscribe(&exact, "ui/button").unwrap().detach();
+        assert_eq!(
+            bus.dispatch_event(&TopicEvent("ui/button", 1)),
+            EventDispatchResult::Stopped
+        );
+        let id = single_subscription.id();
+        assert_eq!(
+            bus.unsubscribe(id, "ui/button"),
+            UnsubscribeOutcome::NotFound
+        );
+        assert_eq!(bus.unsubscribe(id, "ui/+"), UnsubscribeOutcome::Removed);
+        assert_eq!(
+            bus.dispatch_event(&TopicEvent("ui/button", 2)),
+            EventDispatchResult::Finished
+        );
+        assert_eq!(received(&exact), vec![1, 2]);
+        assert_eq!(received(&single), vec![1]);
+        assert_eq!(received(&multi), vec![2]);
+        assert_eq!(bus.unsubscribe_everywhere(&exact), 1);
+        bus.unsubscribe_all_from_filter("ui/#");
+        assert_eq!(
+            bus.dispatch_event(&TopicEvent("ui/button", 3)),
+            EventDispatchResult::NotNeeded
+        );
+    }
+
+    #[test]
+    fn filtered_out_events_are_neither_delivered_nor_counted() {
+        let mut bus = EventBus::default();
+        let odd = Recorder::new(BusRequest::DispatchFailed);
+        let subscription =
+            bus.subscribe_filtered(&odd, Category::Input, |event: &TestEvent| event.1 % 2 == 1);
+        assert_eq!(
+            bus.dispatch_event(&TestEvent(Category::Input, 1)),
+            EventDispatchResult::FinishedWithFailures(1)
+        );
+        let (result, report) = bus.dispatch_event_with_report(&TestEvent(Category::Input, 2));
+        assert_eq!(result, EventDispatchResult::NotNeeded);
+        assert!(report.received.is_empty());
+        assert_eq!(report.filtered, vec![subscription.id()]);
+        let everyone = Recorder::new(BusRequest::NoActionNeeded);
+        bus.subscribe(&everyone, Category::Input).detach();
+        assert_eq!(
+            bus.dispatch_event(&TestEvent(Category::Input, 4)),
+            EventDispatchResult::Finished
+        );
+        assert_eq!(received(&odd), vec![1]);
+        assert_eq!(received(&everyone), vec![4]);
+    }
+
+    #[test]
+    fn events_of_several_categories_reach_each_subscriber_once() {
+        let mut bus = EventBus::default();
+        let both = Recorder::new(BusRequest::NoActionNeeded);
+        let input = Recorder::new(BusRequest::DispatchFailed);
+        let window = Recorder::new(BusRequest::NoActionNeeded);
+        bus.subscribe(&both, Category::Input).detach();
+        bus.subscribe(&both, Category::Window).detach();
+        bus.subscribe(&input, Category::Input).detach();
+        bus.subscribe(&window, Category::Window).detach();
+        let (result, report) = bus.dispatch_event_with_report(&SharedEvent(1));
+        assert_eq!(result, EventDispatchResult::FinishedWithFailures(1));
+        assert_eq!(report.received.len(), 3);
+        assert_eq!(received(&both), vec![1]);
+        assert_eq!(received(&input), vec![1]);
+        assert_eq!(received(&window), vec![1]);
+    }
+
+    struct Panicker;
+
+    impl Subscriber<Category, TestEvent> for Panicker {
+        fn on_event(&self, _: &TestEvent) -> BusRequest {
+            panic!("subscriber failed to handle the event");
+        }
+    }
+
+    #[test]
+    fn quarantined_subscribers_are_passed_over_until_released() {
+        let mut bus = EventBus::default().isolating_panics(PanicPolicy::Quarantine);
+        let panicker = Rc::new(Panicker);
+        let recorder = Recorder::new(BusRequest::NoActionNeeded);
+        let panicker_subscription = bus.subscribe(&panicker, Category::Input);
+        let _recorder_subscription = bus.subscribe(&recorder, Category::Input);
+        let (result, report) = bus.dispatch_event_with_report(&TestEvent(Category::Input, 1));
+        assert_eq!(result, EventDispatchResult::FinishedWithFailures(1));
+        assert_eq!(
+            report.failed,
+            vec![(panicker_subscription.id(), DispatchFailure::Panicked)]
+        );
+        assert_eq!(bus.quarantined(), vec![panicker_subscription.id()]);
+        // Quarantined, so it isn't handed the next event at all
+        assert_eq!(
+            bus.dispatch_event(&TestEvent(Category::Input, 2)),
+            EventDispatchResult::Finished
+        );
+        assert!(bus.release(panicker_subscription.id()));
+        assert!(bus.quarantined().is_empty());
+        assert_eq!(
+            bus.dispatch_event(&TestEvent(Category::Input, 3)),
+            EventDispatchResult::FinishedWithFailures(1)
+        );
+        assert_eq!(*recorder.received.borrow(), vec![1, 2, 3]);
+    }
+
+    #[test]
+    fn undeliverable_events_end_up_with_the_dead_letter_sink() {
+        let dead_letters = Rc::new(RefCell::new(Vec::new()));
+        let sink = Rc::clone(&dead_letters);
+        let mut bus =
+            EventBus::default().collecting_dead_letters(move |event: &TestEvent, reason| {
+                sink.borrow_mut().push((event.1, reason))
+            });
+        let failing = Recorder::new(BusRequest::DispatchFailed);
+        let filtering = Recorder::new(BusRequest::NoActionNeeded);
+        let _failing_subscription = bus.subscribe(&failing, Category::Input);
+        let _filtering_subscription =
+            bus.subscribe_filtered(&filtering, Category::Window, |event: &TestEvent| {
+                event.1 > 10
+            });
+        bus.dispatch_event(&TestEvent(Category::Keyboard, 1));
+        bus.dispatch_event(&TestEvent(Category::Input, 2));
+        // Filtered out on purpose, so not a dead letter
+        bus.dispatch_event(&TestEvent(Category::Window, 3));
+        drop(failing);
+        bus.dispatch_event(&TestEvent(Category::Input, 4));
+        assert_eq!(
+            *dead_letters.borrow(),
+            vec![
+                (1, DeadLetter::NoSubscribers),
+                (2, DeadLetter::AllFailed),
+                (4, DeadLetter::NoSubscribers)
+            ]
+        );
+    }
+
+    #[test]
+    fn late_subscribers_are_handed_the_latest_sticky_event() {
+        let mut bus = EventBus::default();
+        bus.make_sticky(Category::Window);
+        bus.dispatch_event(&TestEvent(Category::Window, 1));
+        bus.dispatch_event(&TestEvent(Category::Window, 2));
+        bus.dispatch_event(&TestEvent(Category::Input, 3));
+        assert_eq!(
+            bus.sticky_event(&Category::Window),
+            Some(&TestEvent(Category::Window, 2))
+        );
+        assert_eq!(bus.sticky_event(&Category::Input), None);
+        let late = Recorder::new(BusRequest::NoActionNeeded);
+        let _late_subscription = bus.subscribe(&late, Category::Window);
+        assert_eq!(*late.received.borrow(), vec![2]);
+        assert_eq!(
+            bus.clear_sticky(&Category::Window),
+            Some(TestEvent(Category::Window, 2))
+        );
+        let later = Recorder::new(BusRequest::NoActionNeeded);
+        let _later_subscription = bus.subscribe(&later, Category::Window);
+        assert!(later.received.borrow().is_empty());
+        // Still sticky, so the next event is kept again
+        bus.dispatch_event(&TestEvent(Category::Window, 4));
+        assert_eq!(
+            bus.sticky_event(&Category::Window),
+            Some(&TestEvent(Category::Window, 4))
+        );
+    }
+
+    #[test]
+    fn history_keeps_the_latest_events_of_each_category() {
+        let mut bus = EventBus::default().recording_history(2);
+        let recorder = Recorder::new(BusRequest::NoActionNeeded);
+        let _subscription = bus.subscribe(&recorder, Category::Keyboard);
+        for id in 1..=3 {
+            bus.dispatch_event(&TestEvent(Category::Keyboard, id));
+        }
+        bus.dispatch_event(&TestEvent(Category::Window, 4));
+        let keyboard = bus.history(&Category::Keyboard);
+        assert_eq!(
+            keyboard
+                .iter()
+                .map(|entry| (entry.sequence, entry.event.1, entry.result.clone()))
+                .collect::<Vec<_>>(),
+            vec![
+                (1, 2, EventDispatchResult::Finished),
+                (2, 3, EventDispatchResult::Finished)
+            ]
+        );
+        let window = bus.history(&Category::Window);
+        assert_eq!(window.len(), 1);
+        assert_eq!(window[0].sequence, 3);
+        assert_eq!(window[0].result, EventDispatchResult::NotNeeded);
+        assert!(bus.history(&Category::Input).is_empty());
+    }
+
+    #[test]
+    fn subscribing_with_replay_hands_over_the_latest_events_of_every_matching_topic() {
+        let mut bus = TopicEventBus::default().recording_history(8);
+        bus.dispatch_event(&TopicEvent("sensors/kitchen/temperature", 1));
+        bus.dispatch_event(&TopicEvent("lights/kitchen", 2));
+        bus.dispatch_event(&TopicEvent("sensors/hall/temperature", 3));
+        bus.dispatch_event(&TopicEvent("sensors/hall/humidity", 4));
+        let recorder = Recorder::new(BusRequest::NoActionNeeded);
+        let _subscription = bus
+            .subscribe_with_replay(&recorder, "sensors/+/temperature", 5)
+            .unwrap();
+        assert_eq!(received(&recorder), vec![1, 3]);
+        bus.dispatch_event(&TopicEvent("sensors/kitchen/temperature", 5));
+        assert_eq!(received(&recorder), vec![1, 3, 5]);
+        assert!(bus
+            .subscribe_with_replay(&recorder, "sensors/#/temperature", 5)
+            .is_err());
+        assert_eq!(received(&recorder), vec![1, 3, 5]);
+    }
+
+    #[test]
+    fn halting_subscribers_also_subscribed_to_every_category_are_handed_the_event_once() {
+        let mut bus = EventBus::default();
+        let halting = Recorder::new(BusRequest::DoNotPropagate);
+        let _subscription = bus.subscribe(&halting, Category::Input);
+        let _all_subscription = bus.subscribe_all(&halting);
+        assert_eq!(
+            bus.dispatch_event(&TestEvent(Category::Input, 1)),
+            EventDispatchResult::Stopped
+        );
+        assert_eq!(received(&halting), vec![1]);
+        let mut priority_bus = PriorityEventBus::default();
+        let _priority_subscription = priority_bus.subscribe(&halting, Category::Input, 0);
+        let _priority_all_subscription = priority_bus.subscribe_all(&halting);
+        priority_bus.dispatch_event(&TestEvent(Category::Input, 2));
+        assert_eq!(received(&halting), vec![1, 2]);
+    }
+
+    #[test]
+    fn topic_subscribers_which_panic_are_quarantined_until_released() {
+        let mut bus = TopicEventBus::default().isolating_panics(PanicPolicy::Quarantine);
+        let recorder = Recorder::new(BusRequest::NoActionNeeded);
+        let panicker = bus
+            .subscribe_fn("sensors/#", |_: &TopicEvent| {
+                panic!("subscriber failed to handle the event")
+            })
+            .unwrap();
+        let _recorder = bus.subscribe(&recorder, "sensors/+").unwrap();
+        let (result, report) = bus.dispatch_event_with_report(&TopicEvent("sensors/hall", 1));
+        assert_eq!(result, EventDispatchResult::FinishedWithFailures(1));
+        assert_eq!(
+            report.failed,
+            vec![(panicker.id(), DispatchFailure::Panicked)]
+        );
+        assert_eq!(bus.quarantined(), vec![panicker.id()]);
+        assert_eq!(
+            bus.dispatch_event(&TopicEvent("sensors/hall", 2)),
+            EventDispatchResult::Finished
+        );
+        assert!(bus.release(panicker.id()));
+        assert_eq!(
+            bus.dispatch_event(&TopicEvent("sensors/hall", 3)),
+            EventDispatchResult::FinishedWithFailures(1)
+        );
+        assert_eq!(received(&recorder), vec![1, 2, 3]);
+    }
+
+    #[test]
+    fn failing_subscribers_stay_subscribed_without_holding_up_the_rest() {
+        let mut bus = EventBus::default();
+        let failing = Recorder::new(BusRequest::DispatchFailed);
+        let later = Recorder::new(BusRequest::NoActionNeeded);
+        let _failing = bus.subscribe(&failing, Category::Input);
+        let _later = bus.subscribe(&later, Category::Input);
+        for n in 1..=2 {
+            assert_eq!(
+                bus.dispatch_event(&TestEvent(Category::Input, n)),
+                EventDispatchResult::FinishedWithFailures(1)
+            );
+        }
+        assert_eq!(received(&failing), vec![1, 2]);
+        assert_eq!(received(&later), vec![1, 2]);
+    }
+
+    /// A priority which can be ordered, but not cloned
+    #[derive(Debug, Eq, PartialEq, Ord, PartialOrd)]
+    struct Layer(u8);
+
+    #[test]
+    fn priorities_need_not_be_cloneable_to_subscribe_and_dispatch() {
+        let mut bus = PriorityEventBus::<Category, TestEvent, Layer>::default();
+        let first = Recorder::new(BusRequest::DoNotPropagate);
+        let later = Recorder::new(BusRequest::NoActionNeeded);
+        let first_subscription = bus.subscribe(&first, Category::Input, Layer(1));
+        let _later = bus.subscribe(&later, Category::Input, Layer(2));
+        assert_eq!(
+            bus.dispatch_event(&TestEvent(Category::Input, 1)),
+            EventDispatchResult::Stopped
+        );
+        assert_eq!(
+            bus.unsubscribe_from_category(first_subscription.id(), &Category::Input),
+            UnsubscribeOutcome::Removed
+        );
+        assert_eq!(
+            bus.dispatch_event(&TestEvent(Category::Input, 2)),
+            EventDispatchResult::Finished
+        );
+        assert_eq!(received(&later), vec![2]);
+    }
+
+    /// Follows up on every `Input` event by queueing a `Window` event for the next frame
+    struct FrameRelay {
+        queue: DispatchQueue<EventBus<Category, TestEvent>, TestEvent>,
+    }
+
+    impl Subscriber<Category, TestEvent> for FrameRelay {
+        fn on_event(&self, event: &TestEvent) -> BusRequest {
+            self.queue
+                .queue_event(TestEvent(Category::Window, event.1 + 1));
+            BusRequest::NoActionNeeded
+        }
+    }
+
+    #[test]
+    fn subscribers_queue_events_for_the_next_drain_through_the_dispatch_queue() {
+        let mut bus = EventBus::default();
+        let relay = Rc::new(FrameRelay {
+            queue: bus.dispatch_queue(),
+        });
+        let recorder = Recorder::new(BusRequest::NoActionNeeded);
+        let _relay = bus.subscribe(&relay, Category::Input);
+        let _recorder = bus.subscribe(&recorder, Category::Window);
+        bus.queue_event(TestEvent(Category::Input, 1));
+        assert_eq!(bus.drain().len(), 1);
+        assert!(received(&recorder).is_empty());
+        assert_eq!(bus.queued_events(), 1);
+        assert_eq!(
+            bus.drain(),
+            vec![(
+                TestEvent(Category::Window, 2),
+                EventDispatchResult::Finished
+            )]
+        );
+        assert_eq!(received(&recorder), vec![2]);
+    }
+
+    #[test]
+    fn dropping_a_subscription_removes_its_entries_before_the_bus_is_next_used() {
+        let mut bus = EventBus::<Category, TestEvent>::default();
+        let dropped = Recorder::new(BusRequest::NoActionNeeded);
+        let kept = Recorder::new(BusRequest::NoActionNeeded);
+        let subscription = bus.subscribe_many(&dropped, vec![Category::Input, Category::Window]);
+        let everywhere = bus.subscribe_all(&dropped);
+        drop(subscription);
+        drop(everywhere);
+        bus.subscribe(&kept, Category::Input).detach();
+        assert_eq!(bus.channels[&Category::Input].len(), 1);
+        assert!(bus.channels[&Category::Window].is_empty());
+        assert!(bus.catch_all.is_empty());
+    }
+
+    #[test]
+    fn topic_filters_emptied_while_dispatching_are_pruned_from_the_trie() {
+        let mut bus = TopicEventBus::default();
+        let once = Recorder::new(BusRequest::Unsubscribe);
+        bus.subscribe(&once, "sensors/+/temperature")
+            .unwrap()
+            .detach();
+        bus.dispatch_event(&TopicEvent("sensors/kitchen/temperature", 1));
+        assert_eq!(received(&once), vec![1]);
+        // Only the root's lists are left
+        assert_eq!(bus.channels.lists_mut().len(), 2);
+    }
+}
@@ -0,0 +1,129 @@
+/*
+    ABSTRACT: Definition of a single-thread queue of commands which subscribers (see subscribe.rs)
+    can defer until their event bus (see bus.rs) has finished dispatching the current event
+*/
+use crate::{
+    rc::{Event, EventBus, PriorityEventBus, Subscriber},
+    types::SubscriberId,
+};
+use std::cell::RefCell;
+use std::collections::VecDeque;
+use std::hash::Hash;
+use std::rc::Rc;
+
+/// A command deferred until after the current dispatch, see `DispatchQueue`
+pub(crate) enum Command<B, E> {
+    Publish(E),
+    Apply(Box<dyn FnOnce(&mut B)>),
+}
+
+/// A handle onto a bus `B`'s queue of deferred commands, through which `Subscriber`s can publish events `E` and change subscriptions from inside `on_event`
+///
+/// A `Subscriber` can't touch the bus while it's dispatching to it (and the bus is already borrowed), so instead it holds onto a `DispatchQueue` (see `EventBus::dispatch_queue`).
+/// Once the bus has finished dispatching the current event, it runs every queued command in the order they were queued in, including any queued while doing so.
+///
+/// ### Notes
+/// - Events published through the queue are dispatched in full, but their `EventDispatchResult`s are not reported anywhere.
+/// - Commands only run once the bus next finishes dispatching an event, so queueing commands outside of `on_event` leaves them waiting until then.
+pub struct DispatchQueue<B, E> {
+    commands: Rc<RefCell<VecDeque<Command<B, E>>>>,
+}
+
+impl<B, E> Clone for DispatchQueue<B, E> {
+    fn clone(&self) -> Self {
+        Self {
+            commands: Rc::clone(&self.commands),
+        }
+    }
+}
+
+impl<B, E> Default for DispatchQueue<B, E> {
+    fn default() -> Self {
+        Self {
+            commands: Rc::new(RefCell::new(VecDeque::new())),
+        }
+    }
+}
+
+impl<B, E> DispatchQueue<B, E> {
+    /// Queues the given event to be dispatched once the bus has finished dispatching the current one
+    pub fn publish(&self, event: E) {
+        self.commands
+            .borrow_mut()
+            .push_back(Command::Publish(event));
+    }
+
+    /// Queues the given command to be run against the bus once it has finished dispatching the current event
+    pub fn defer<F: FnOnce(&mut B) + 'static>(&self, command: F) {
+        self.commands
+            .borrow_mut()
+            .push_back(Command::Apply(Box::new(command)));
+    }
+
+    /// Takes the next command off of the queue, if there is one
+    pub(crate) fn pop(&self) -> Option<Command<B, E>> {
+        self.commands.borrow_mut().pop_front()
+    }
+}
+
+impl<T, E> DispatchQueue<EventBus<T, E>, E>
+where
+    T: Eq + PartialEq + Hash + Clone + 'static,
+    E: Event<T> + Eq + PartialEq + Hash + Clone + 'static,
+{
+    /// Queues the given `Subscriber` to be subscribed to the given category, unless it's dropped before then (see `EventBus::subscribe`)
+    ///
+    /// The `Subscriber` stays subscribed until it's unsubscribed (or dropped), as if its `Subscription` had been detached.
+    pub fn subscribe<S: Subscriber<T, E> + 'static>(&self, subscriber: &Rc<S>, to_category: T) {
+        let subscriber = Rc::downgrade(subscriber);
+        self.defer(move |bus| {
+            if let Some(subscriber) = subscriber.upgrade() {
+                bus.subscribe(&subscriber, to_category).detach();
+            }
+        });
+    }
+
+    /// Queues the given event to be queued on the bus in turn, so it's dispatched by the bus's next `drain` or `pump` rather than once the bus has finished dispatching the current event (see `EventBus::queue_event`)
+    pub fn queue_event(&self, event: E) {
+        self.defer(move |bus| bus.queue_event(event));
+    }
+
+    /// Queues the `Subscriber` with the given id to be unsubscribed from the given category (see `EventBus::unsubscribe`)
+    pub fn unsubscribe(&self, id: SubscriberId, from_category: T) {
+        self.defer(move |bus| {
+            bus.unsubscribe(id, from_category);
+        });
+    }
+}
+
+impl<T, E, P> DispatchQueue<PriorityEventBus<T, E, P>, E>
+where
+    T: Eq + PartialEq + Hash + Clone + 'static,
+    E: Event<T> + Eq + PartialEq + Hash + Clone + 'static,
+    P: Ord + 'static,
+{
+    /// Queues the given `Subscriber` to be subscribed to the given priority segment of the given category, unless it's dropped before then (see `PriorityEventBus::subscribe`)
+    ///
+    /// The `Subscriber` stays subscribed until it's unsubscribed (or dropped), as if its `Subscription` had been detached.
+    pub fn subscribe<S: Subscriber<T, E> + 'static>(
+        &self,
+        subscriber: &Rc<S>,
+        to_category: T,
+        with_priority: P,
+    ) {
+        let subscriber = Rc::downgrade(subscriber);
+        self.defer(move |bus| {
+            if let Some(subscriber) = subscriber.upgrade() {
+                bus.subscribe(&subscriber, to_category, with_priority)
+                    .detach();
+            }
+        });
+    }
+
+    /// Queues the `Subscriber` with the given id to be unsubscribed from the given priority segment of the given category (see `PriorityEventBus::unsubscribe`)
+    pub fn unsubscribe(&self, id: SubscriberId, from_category: T, with_priority: P) {
+        self.defer(move |bus| {
+            bus.unsubscribe(id, &from_category, &with_priority);
+        });
+    }
+}
@@ -3,20 +3,99 @@
     intermediary event bus (see bus.rs) which dispatches relevant generic events
     that are published to them by one or more publishers (see publish.rs)
 */
-use crate::{rc::Event, types::BusRequest};
+use crate::{
+    rc::Event,
+    types::{BusRequest, FnSubscriber, SubscriberId},
+};
+use std::cell::{Cell, RefCell};
 use std::hash::Hash;
-use uuid::Uuid;
+use std::rc::{Rc, Weak};
 
 /// A generic, single-thread `Subscriber` which subscribes to an `EventBus` to receive events `E` of category `T`, which are published by a `Publisher`.
 ///
 /// - `T` is meant to be implemented by the module consumer as an enum, depicting the various categories an event can belong to.
 ///
 /// - `E` is meant to be implemented by the module consumer as an enum, depicting the individual events which exist in the system. See `Event`.
+///
+/// A `Subscriber` needs no id of its own, the bus hands it a `SubscriberId` when it subscribes (see `Subscription::id`).
 pub trait Subscriber<T, E>
 where
     T: Eq + PartialEq + Hash + Clone + 'static,
     E: Event<T> + Eq + PartialEq + Hash + Clone + 'static,
 {
-    fn id(&self) -> &Uuid;
     fn on_event(&self, event: &E) -> BusRequest;
+
+    /// Handles the given event with write access to this `Subscriber`, which is how a bus hands events to the `Subscriber`s registered through its `subscribe_mut`
+    ///
+    /// Defaults to `on_event`, so only a `Subscriber` which mutates itself needs to implement it
+    fn on_event_mut(&mut self, event: &E) -> BusRequest {
+        self.on_event(event)
+    }
+}
+
+impl<T, E, F> Subscriber<T, E> for FnSubscriber<F>
+where
+    T: Eq + PartialEq + Hash + Clone + 'static,
+    E: Event<T> + Eq + PartialEq + Hash + Clone + 'static,
+    F: Fn(&E) -> BusRequest,
+{
+    fn on_event(&self, event: &E) -> BusRequest {
+        (self.function)(event)
+    }
+}
+
+/// Where a `Subscription` notes down its id once it's dropped, without keeping the bus's `DroppedSubscriptions` alive
+pub(crate) type DroppedHandle = Weak<RefCell<Vec<SubscriberId>>>;
+
+/// A guard returned by a bus's `subscribe` methods, which keeps the `Subscriber` subscribed for as long as it's held
+///
+/// ### Notes
+/// - Dropping the `Subscription` unsubscribes the `Subscriber` right away, it won't be handed any more events. Call `detach` to keep it subscribed for good instead.
+/// - The bus removes the `Subscriber`'s entry before it next subscribes, unsubscribes or dispatches anything, even if it never comes across the entry while dispatching.
+#[must_use = "dropping a `Subscription` unsubscribes its `Subscriber` right away, call `detach` to keep it subscribed"]
+pub struct Subscription {
+    id: SubscriberId,
+    subscribed: Rc<Cell<bool>>,
+    dropped: DroppedHandle,
+    detached: bool,
+}
+
+impl Subscription {
+    pub(crate) fn new(
+        id: SubscriberId,
+        subscribed: Rc<Cell<bool>>,
+        dropped: DroppedHandle,
+    ) -> Self {
+        Self {
+            id,
+            subscribed,
+            dropped,
+            detached: false,
+        }
+    }
+
+    /// The id the bus knows the `Subscriber` by, which it can be unsubscribed by and which appears in a `DispatchReport`
+    pub fn id(&self) -> SubscriberId {
+        self.id
+    }
+
+    /// Lets go of this `Subscription` without unsubscribing the `Subscriber`, which then stays subscribed until it's unsubscribed (or dropped) some other way
+    pub fn detach(mut self) {
+        self.detached = true;
+    }
+
+    /// Unsubscribes the `Subscriber` right away, the same as dropping this `Subscription`
+    pub fn unsubscribe(self) {}
+}
+
+impl Drop for Subscription {
+    fn drop(&mut self) {
+        if !self.detached {
+            self.subscribed.set(false);
+            // The bus removes our entry before it next touches its subscriber lists, unless it's gone already
+            if let Some(dropped) = self.dropped.upgrade() {
+                dropped.borrow_mut().push(self.id);
+            }
+        }
+    }
 }
@@ -1,7 +1,197 @@
-use crate::rc::Subscriber;
+use crate::{
+    rc::{Event, Subscriber, Subscription},
+    types::{BusRequest, DeadLetter, Delivery, DispatchFailure, Isolation, SubscriberId},
+};
+use std::cell::{Cell, RefCell};
 use std::collections::{BTreeMap, HashMap};
-use std::rc::Weak;
+use std::hash::Hash;
+use std::rc::{Rc, Weak};
 
-pub(crate) type SubscriberMap<T, E> = HashMap<T, Vec<Weak<dyn Subscriber<T, E>>>>;
-pub(crate) type PrioritySubscriberMap<T, E, P> =
-    HashMap<T, BTreeMap<P, Vec<Weak<dyn Subscriber<T, E>>>>>;
+pub(crate) type SubscriberMap<T, E> = HashMap<T, Vec<SubscriberRef<T, E>>>;
+pub(crate) type PrioritySubscriberMap<T, E, P> = HashMap<T, BTreeMap<P, Vec<SubscriberRef<T, E>>>>;
+/// A predicate deciding which events a `Subscriber` is handed at all, see `EventBus::subscribe_filtered`
+pub(crate) type EventFilter<E> = Rc<dyn Fn(&E) -> bool>;
+/// Where a bus hands the events which don't reach a single `Subscriber`, see `EventBus::collecting_dead_letters`
+pub(crate) type DeadLetterSink<E> = Box<dyn Fn(&E, DeadLetter)>;
+
+/// The ids of the `Subscription`s dropped since a bus last removed their entries, shared between that bus and every `Subscription` it hands out
+#[derive(Default)]
+pub(crate) struct DroppedSubscriptions(Rc<RefCell<Vec<SubscriberId>>>);
+
+impl DroppedSubscriptions {
+    /// Takes the ids noted down since this was last called
+    pub(crate) fn take(&self) -> Vec<SubscriberId> {
+        self.0.take()
+    }
+}
+
+/// A non-owning reference to a subscribed `Subscriber`, which also knows how that `Subscriber` wants to be handed events
+pub(crate) struct SubscriberRef<T, E> {
+    // The id the bus handed out to the `Subscriber` when it subscribed
+    id: SubscriberId,
+    subscriber: Subscribed<T, E>,
+    // Cleared once the `Subscription` guarding this entry is dropped, at which point the `Subscriber` is as good as unsubscribed
+    subscribed: Rc<Cell<bool>>,
+    // Decides which events the `Subscriber` is handed at all, if it subscribed through `subscribe_filtered`
+    filter: Option<EventFilter<E>>,
+}
+
+/// The different ways a `Subscriber` can be subscribed to a bus
+enum Subscribed<T, E> {
+    /// Handed events through `on_event`, see `EventBus::subscribe`
+    Shared(Weak<dyn Subscriber<T, E>>),
+    /// Handed events through `on_event_mut`, see `EventBus::subscribe_mut`
+    Mutable(Weak<RefCell<dyn Subscriber<T, E>>>),
+    /// Handed events through `on_event`, and owned by the bus itself, see `EventBus::subscribe_fn`
+    Owned(Rc<dyn Subscriber<T, E>>),
+}
+
+/// A copy of a `SubscriberRef` shares its id and `Subscription` with the original, see `EventBus::subscribe_many`
+impl<T, E> Clone for SubscriberRef<T, E> {
+    fn clone(&self) -> Self {
+        Self {
+            id: self.id,
+            subscriber: match &self.subscriber {
+                Subscribed::Shared(subscriber) => Subscribed::Shared(Weak::clone(subscriber)),
+                Subscribed::Mutable(subscriber) => Subscribed::Mutable(Weak::clone(subscriber)),
+                Subscribed::Owned(subscriber) => Subscribed::Owned(Rc::clone(subscriber)),
+            },
+            subscribed: Rc::clone(&self.subscribed),
+            filter: self.filter.clone(),
+        }
+    }
+}
+
+impl<T, E> SubscriberRef<T, E>
+where
+    T: Eq + PartialEq + Hash + Clone + 'static,
+    E: Event<T> + Eq + PartialEq + Hash + Clone + 'static,
+{
+    pub(crate) fn shared(subscriber: Weak<dyn Subscriber<T, E>>) -> Self {
+        Self::new(Subscribed::Shared(subscriber))
+    }
+
+    pub(crate) fn mutable(subscriber: Weak<RefCell<dyn Subscriber<T, E>>>) -> Self {
+        Self::new(Subscribed::Mutable(subscriber))
+    }
+
+    pub(crate) fn owned(subscriber: Rc<dyn Subscriber<T, E>>) -> Self {
+        Self::new(Subscribed::Owned(subscriber))
+    }
+
+    fn new(subscriber: Subscribed<T, E>) -> Self {
+        Self {
+            id: SubscriberId::next(),
+            subscriber,
+            subscribed: Rc::new(Cell::new(true)),
+            filter: None,
+        }
+    }
+
+    /// Has the `Subscriber` only handed events which match the given predicate, see `EventBus::subscribe_filtered`
+    pub(crate) fn filtered_by<F: Fn(&E) -> bool + 'static>(mut self, predicate: F) -> Self {
+        self.filter = Some(Rc::new(predicate));
+        self
+    }
+
+    /// A guard which unsubscribes the `Subscriber` once it's dropped, noting its id down in the given bus's `DroppedSubscriptions`
+    pub(crate) fn subscription(&self, dropped: &DroppedSubscriptions) -> Subscription {
+        Subscription::new(
+            self.id,
+            Rc::clone(&self.subscribed),
+            Rc::downgrade(&dropped.0),
+        )
+    }
+
+    /// Whether the `Subscriber` has been dropped by its owner, or the `Subscription` guarding it has been
+    pub(crate) fn is_dropped(&self) -> bool {
+        !self.subscribed.get()
+            || match &self.subscriber {
+                Subscribed::Shared(subscriber) => subscriber.strong_count() == 0,
+                Subscribed::Mutable(subscriber) => subscriber.strong_count() == 0,
+                Subscribed::Owned(_) => false,
+            }
+    }
+
+    /// The id the bus handed out to the `Subscriber` when it subscribed
+    pub(crate) fn id(&self) -> SubscriberId {
+        self.id
+    }
+
+    /// Where the `Subscriber` lives, which tells it apart from every other `Subscriber` however many times it's subscribed
+    pub(crate) fn address(&self) -> *const () {
+        match &self.subscriber {
+            Subscribed::Shared(subscriber) => Weak::as_ptr(subscriber) as *const (),
+            Subscribed::Mutable(subscriber) => Weak::as_ptr(subscriber) as *const (),
+            Subscribed::Owned(subscriber) => Rc::as_ptr(subscriber) as *const (),
+        }
+    }
+
+    /// Whether this refers to the given `Subscriber`, dropped or not, which is told apart by where it lives rather than by its id
+    pub(crate) fn refers_to<S: ?Sized>(&self, subscriber: &Rc<S>) -> bool {
+        self.address() == Rc::as_ptr(subscriber) as *const ()
+    }
+
+    /// Whether the `Subscriber` wants to be handed the given event at all, see `EventBus::subscribe_filtered`
+    pub(crate) fn accepts(&self, event: &E) -> bool {
+        self.filter.as_ref().map_or(true, |filter| filter(event))
+    }
+
+    /// Hands the given event to the `Subscriber`, if it hasn't been dropped (see `is_dropped`) and doesn't filter the event out
+    pub(crate) fn deliver(&self, event: &E) -> Delivery {
+        if !self.subscribed.get() {
+            // The `Subscription` guarding this subscriber was dropped
+            return Delivery::Dropped;
+        }
+        if !self.is_dropped() && !self.accepts(event) {
+            return Delivery::Filtered(self.id);
+        }
+        match &self.subscriber {
+            Subscribed::Shared(subscriber) => match subscriber.upgrade() {
+                Some(subscriber) => Delivery::Handled(self.id, subscriber.on_event(event)),
+                // Found an invalid reference to a subscriber (which was probably dropped by the owner)
+                None => Delivery::Dropped,
+            },
+            Subscribed::Mutable(subscriber) => match subscriber.upgrade() {
+                Some(subscriber) => match subscriber.try_borrow_mut() {
+                    Ok(mut subscriber) => {
+                        Delivery::Handled(self.id, subscriber.on_event_mut(event))
+                    }
+                    // The subscriber is borrowed elsewhere (i.e. it's the one dispatching this event)
+                    Err(_) => Delivery::Failed(self.id, DispatchFailure::LockBusy),
+                },
+                // Found an invalid reference to a subscriber (which was probably dropped by the owner)
+                None => Delivery::Dropped,
+            },
+            Subscribed::Owned(subscriber) => Delivery::Handled(self.id, subscriber.on_event(event)),
+        }
+    }
+
+    /// Hands the given event to the `Subscriber` as `deliver` does, through the given panic isolation if the bus has any (see `EventBus::isolating_panics`)
+    pub(crate) fn deliver_isolated(
+        &self,
+        event: &E,
+        isolation: Option<&mut Isolation>,
+    ) -> Delivery {
+        match isolation {
+            Some(isolation) => isolation.deliver(self.id, || self.deliver(event)),
+            None => self.deliver(event),
+        }
+    }
+
+    /// Hands the given events to the `Subscriber` one after the other before it's subscribed (see `EventBus::make_sticky` and `EventBus::subscribe_with_replay`), stopping as soon as it no longer wants them
+    ///
+    /// Returns whether the `Subscriber` should still be subscribed afterwards, i.e. it didn't unsubscribe itself, wasn't unsubscribed for panicking, and hasn't been dropped.
+    pub(crate) fn replay(&self, events: &[E], mut isolation: Option<&mut Isolation>) -> bool {
+        events.iter().all(|event| {
+            !matches!(
+                self.deliver_isolated(event, isolation.as_deref_mut()),
+                Delivery::Handled(
+                    _,
+                    BusRequest::Unsubscribe | BusRequest::UnsubscribeAndDoNotPropagate
+                ) | Delivery::Evicted(..)
+                    | Delivery::Dropped
+            )
+        })
+    }
+}
@@ -5,17 +5,31 @@
 */
 #![allow(dead_code)]
 use crate::{
-    sync::{types::*, Event, Subscriber},
+    sync::{
+        pool::WorkerPool,
+        queue::{Command, DispatchQueue},
+        types::*,
+        Event, Subscriber, Subscription,
+    },
+    topic::{filter_matches, parse_filter, TopicTrie},
     types::*,
 };
-use std::collections::{BTreeMap, HashMap};
+use std::collections::{BTreeMap, HashMap, HashSet, VecDeque};
 use std::hash::Hash;
-use std::sync::{Arc, RwLock, Weak};
+use std::num::NonZeroUsize;
+use std::slice;
+use std::sync::{mpsc, Arc, RwLock};
+use std::thread;
+use std::time::{Duration, Instant};
 
 /// Thread-safe datastructure responsible for dispatching events from `Publisher`s to `Subscriber`s
 ///
 /// This keeps the respective Pub/Sub systems decoupled from each other
 ///
+/// `Subscriber`s of a category are notified in the order they subscribed in, followed by those subscribed to every category (see `subscribe_all`)
+///
+/// A bus created through `hierarchical` also hands each event to the `Subscriber`s of every ancestor of its category (see `HierarchicalCategory`)
+///
 /// This should be wrapped in a Arc<RwLock<EventBus>>
 pub struct EventBus<T, E>
 where
@@ -25,6 +39,30 @@ where
     // We hold a std::sync::Weak (Arc which holds non-owning reference) to not prevent dropping and to avoid circular references to an Arc
     // We can deal with subscribers that get dropped by just removing them from our map if we find they did get dropped
     channels: SubscriberMap<T, E>,
+    // Subscribers handed every event, whatever its category (see `subscribe_all`)
+    catch_all: Vec<SubscriberRef<T, E>>,
+    // How events are routed through the ancestors of their category, if this bus was created through `hierarchical`
+    routing: Option<Routing<T>>,
+    // How subscribers which panic are dealt with, if this bus was set up through `isolating_panics`
+    isolation: Option<Isolation>,
+    // How subscribers with poisoned locks are dealt with, if this bus was set up through `handling_poison`
+    poison: Option<PoisonPolicy>,
+    // Categories whose latest event is kept around for late subscribers (see `make_sticky`)
+    sticky_categories: HashSet<T>,
+    // The latest sticky event of each category, handed to every subscriber which subscribes to that category later on
+    sticky: HashMap<T, E>,
+    // Where events which don't reach a single subscriber are handed, if this bus was set up through `collecting_dead_letters`
+    dead_letters: Option<DeadLetterSink<E>>,
+    // The latest events dispatched to each category, if this bus was set up through `recording_history`
+    history: Option<History<T, E, EventDispatchResult>>,
+    // The ids of `Subscription`s dropped since we last removed their entries (see `remove_dropped`)
+    dropped: DroppedSubscriptions,
+    // Commands deferred by subscribers until we've finished dispatching the current event
+    deferred: DispatchQueue<Self, E>,
+    // Events waiting to be dispatched by `drain` or `pump`, in the order they were queued in
+    queued: VecDeque<E>,
+    // Events waiting to be retried on subscribers a non-blocking dispatch found busy, if this bus was set up through `retrying`
+    retries: Option<RetryQueue<T, E>>,
 }
 
 impl<T, E> Default for EventBus<T, E>
@@ -35,6 +73,18 @@ where
     fn default() -> Self {
         Self {
             channels: HashMap::default(),
+            catch_all: Vec::new(),
+            routing: None,
+            isolation: None,
+            poison: None,
+            sticky_categories: HashSet::new(),
+            sticky: HashMap::new(),
+            dead_letters: None,
+            history: None,
+            dropped: DroppedSubscriptions::default(),
+            deferred: DispatchQueue::default(),
+            queued: VecDeque::new(),
+            retries: None,
         }
     }
 }
@@ -44,56 +94,326 @@ where
     T: Eq + PartialEq + Hash + Clone + Send + Sync + 'static,
     E: Event<T> + Eq + PartialEq + Hash + Clone + Send + Sync + 'static,
 {
+    /// Creates an `EventBus` which also hands each event to the `Subscriber`s of every ancestor of its category, in the given order
+    pub fn hierarchical(order: RoutingOrder) -> Self
+    where
+        T: HierarchicalCategory,
+    {
+        Self {
+            routing: Some(Routing::new(order)),
+            ..Self::default()
+        }
+    }
+
+    /// Has this `EventBus` catch any panic a `Subscriber` throws while handling an event, rather than letting it unwind through the whole dispatch, and deal with that `Subscriber` as the given policy says (see `PanicPolicy`)
+    pub fn isolating_panics(mut self, policy: PanicPolicy) -> Self {
+        self.isolation = Some(Isolation::new(policy));
+        self
+    }
+
+    /// Has this `EventBus` deal with `Subscriber`s whose lock was poisoned by an earlier panic as the given policy says, rather than failing to hand each of them every event from then on (see `PoisonPolicy`)
+    pub fn handling_poison(mut self, policy: PoisonPolicy) -> Self {
+        self.poison = Some(policy);
+        self
+    }
+
+    /// The id of every `Subscriber` quarantined after panicking (see `PanicPolicy::Quarantine`), in the order they were quarantined in
+    pub fn quarantined(&self) -> Vec<SubscriberId> {
+        self.isolation
+            .as_ref()
+            .map_or_else(Vec::new, Isolation::quarantined)
+    }
+
+    /// Lets the quarantined `Subscriber` with the given id be handed events again
+    ///
+    /// ### Returns
+    /// - `bool`: whether the `Subscriber` was quarantined at all.
+    pub fn release(&mut self, id: SubscriberId) -> bool {
+        self.isolation
+            .as_mut()
+            .map_or(false, |isolation| isolation.release(id))
+    }
+
+    /// Has this `EventBus` hold on to events which a non-blocking dispatch couldn't hand to a busy `Subscriber`, and retry them later on within the given limits
+    ///
+    /// ### Notes
+    /// - Pending events are retried by `retry_pending`, and at the start of every non-blocking dispatch if the policy is `automatic`.
+    /// - A `Subscriber` with events still waiting has every later event queued behind them, rather than handed to it out of order.
+    /// - A `Subscriber` which has an event queued up doesn't count as failing to handle it, and shows up in a `DispatchReport`'s `queued` instead, unless the policy leaves no room to queue events at all.
+    /// - Blocking and timed dispatches don't skip busy `Subscriber`s in the first place, so they leave the retry queue alone.
+    pub fn retrying(mut self, policy: RetryPolicy) -> Self {
+        self.retries = Some(RetryQueue::new(policy));
+        self
+    }
+
+    /// Has this `EventBus` hand every event which doesn't reach a single `Subscriber` to the given sink, along with why it didn't (see `DeadLetter`)
+    pub fn collecting_dead_letters<F: Fn(&E, DeadLetter) + Send + Sync + 'static>(
+        mut self,
+        sink: F,
+    ) -> Self {
+        self.dead_letters = Some(Box::new(sink));
+        self
+    }
+
+    /// Has this `EventBus` keep the latest `capacity` events dispatched to each category, along with what became of them (see `HistoryEntry`)
+    pub fn recording_history(mut self, capacity: usize) -> Self {
+        self.history = Some(History::new(capacity));
+        self
+    }
+
+    /// The latest events dispatched to the given category, oldest first, if this `EventBus` was set up through `recording_history`
+    pub fn history(&self, category: &T) -> Vec<&HistoryEntry<E, EventDispatchResult>> {
+        self.history
+            .as_ref()
+            .map_or_else(Vec::new, |history| history.entries(category))
+    }
+
     /// Adds the given `Subscriber` to a subscriber list to receive published messages of the given event category
+    ///
+    /// ### Returns
+    /// - `Subscription`: a guard which unsubscribes the `Subscriber` once it's dropped, call `detach` on it to keep the `Subscriber` subscribed instead.
     pub fn subscribe<S: Subscriber<T, E> + 'static>(
         &mut self,
         subscriber: &Arc<RwLock<S>>,
         to_category: T,
-    ) {
+    ) -> Subscription {
+        let subscriber = SubscriberRef::new(
+            Arc::downgrade(&(subscriber.clone() as Arc<RwLock<dyn Subscriber<T, E> + 'static>>)),
+            false,
+        );
+        let subscription = subscriber.subscription(&self.dropped);
+        self.add(subscriber, to_category);
+        subscription
+    }
+
+    /// Adds the given `Subscriber` to a subscriber list to receive published messages of the given event category through `on_event_mut`, under a write-lock
+    ///
+    /// ### Returns
+    /// - `Subscription`: a guard which unsubscribes the `Subscriber` once it's dropped, call `detach` on it to keep the `Subscriber` subscribed instead.
+    pub fn subscribe_mut<S: Subscriber<T, E> + 'static>(
+        &mut self,
+        subscriber: &Arc<RwLock<S>>,
+        to_category: T,
+    ) -> Subscription {
+        let subscriber = SubscriberRef::new(
+            Arc::downgrade(&(subscriber.clone() as Arc<RwLock<dyn Subscriber<T, E> + 'static>>)),
+            true,
+        );
+        let subscription = subscriber.subscription(&self.dropped);
+        self.add(subscriber, to_category);
+        subscription
+    }
+
+    /// Adds the given `Subscriber` to a subscriber list to receive published messages of the given event category, but only those matching the given predicate
+    ///
+    /// ### Returns
+    /// - `Subscription`: a guard which unsubscribes the `Subscriber` once it's dropped, call `detach` on it to keep the `Subscriber` subscribed instead.
+    ///
+    /// ### Notes
+    /// - The predicate is checked before the `Subscriber` is handed each event, and an event it rejects is never delivered: it shows up in a `DispatchReport`'s `filtered` rather than its `received`, and doesn't count towards the dispatch result.
+    pub fn subscribe_filtered<
+        S: Subscriber<T, E> + 'static,
+        F: Fn(&E) -> bool + Send + Sync + 'static,
+    >(
+        &mut self,
+        subscriber: &Arc<RwLock<S>>,
+        to_category: T,
+        predicate: F,
+    ) -> Subscription {
+        let subscriber = SubscriberRef::new(
+            Arc::downgrade(&(subscriber.clone() as Arc<RwLock<dyn Subscriber<T, E> + 'static>>)),
+            false,
+        )
+        .filtered_by(predicate);
+        let subscription = subscriber.subscription(&self.dropped);
+        self.add(subscriber, to_category);
+        subscription
+    }
+
+    /// Adds the given `Subscriber` to the subscriber lists of each of the given event categories at once
+    ///
+    /// ### Returns
+    /// - `Subscription`: a single guard for every one of those subscriptions, which unsubscribes the `Subscriber` from all of them once it's dropped. They all share its id, so `unsubscribe` still unsubscribes the `Subscriber` from one category at a time.
+    pub fn subscribe_many<S: Subscriber<T, E> + 'static, I: IntoIterator<Item = T>>(
+        &mut self,
+        subscriber: &Arc<RwLock<S>>,
+        to_categories: I,
+    ) -> Subscription {
+        let subscriber = SubscriberRef::new(
+            Arc::downgrade(&(subscriber.clone() as Arc<RwLock<dyn Subscriber<T, E> + 'static>>)),
+            false,
+        );
+        let subscription = subscriber.subscription(&self.dropped);
+        for to_category in to_categories {
+            self.add(subscriber.clone(), to_category);
+        }
+        subscription
+    }
+
+    /// Adds the given `Subscriber` to receive every event published on this `EventBus`, whatever its category
+    ///
+    /// ### Notes
+    /// - It's notified after the `Subscriber`s of the event's category, even if one of those halted propagation. Halting propagation itself only keeps the event from the rest of the `Subscriber`s subscribed to every category.
+    /// - Unlike any other `Subscriber`, it isn't subscribed to a category it can be unsubscribed from, so it stays subscribed until its `Subscription` is dropped, `unsubscribe_everywhere` is called for it, or it's dropped.
+    ///
+    /// ### Returns
+    /// - `Subscription`: a guard which unsubscribes the `Subscriber` once it's dropped, call `detach` on it to keep the `Subscriber` subscribed instead.
+    pub fn subscribe_all<S: Subscriber<T, E> + 'static>(
+        &mut self,
+        subscriber: &Arc<RwLock<S>>,
+    ) -> Subscription {
+        self.remove_dropped();
+        let subscriber = SubscriberRef::new(
+            Arc::downgrade(&(subscriber.clone() as Arc<RwLock<dyn Subscriber<T, E> + 'static>>)),
+            false,
+        );
+        let subscription = subscriber.subscription(&self.dropped);
+        self.catch_all.push(subscriber);
+        subscription
+    }
+
+    /// Subscribes the given closure to receive published messages of the given event category, with this `EventBus` taking ownership of it
+    ///
+    /// ### Returns
+    /// - `FnHandle<T>`: a handle which can be handed to `unsubscribe_fn` to unsubscribe the closure again.
+    pub fn subscribe_fn<F: Fn(&E) -> BusRequest + Send + Sync + 'static>(
+        &mut self,
+        to_category: T,
+        function: F,
+    ) -> FnHandle<T> {
+        let subscriber = SubscriberRef::owned(Arc::new(RwLock::new(FnSubscriber::new(function))));
+        let handle = FnHandle {
+            id: subscriber.id(),
+            subscribed_to: to_category.clone(),
+        };
+        self.add(subscriber, to_category);
+        handle
+    }
+
+    /// Adds the given `Subscriber` to a subscriber list to receive published messages of the given event category, handing it the latest `last` events dispatched to that category first, oldest first (see `HistoryEntry`)
+    ///
+    /// ### Notes
+    /// - The category's sticky event (see `make_sticky`) is only handed to the `Subscriber` if there are no events to replay, as it's the latest of them otherwise.
+    ///
+    /// ### Returns
+    /// - `Subscription`: a guard which unsubscribes the `Subscriber` once it's dropped, call `detach` on it to keep the `Subscriber` subscribed instead.
+    pub fn subscribe_with_replay<S: Subscriber<T, E> + 'static>(
+        &mut self,
+        subscriber: &Arc<RwLock<S>>,
+        to_category: T,
+        last: usize,
+    ) -> Subscription {
+        let subscriber = SubscriberRef::new(
+            Arc::downgrade(&(subscriber.clone() as Arc<RwLock<dyn Subscriber<T, E> + 'static>>)),
+            false,
+        );
+        let subscription = subscriber.subscription(&self.dropped);
+        let events = self.history.as_ref().map_or_else(Vec::new, |history| {
+            history.latest(|category| *category == to_category, last)
+        });
+        if events.is_empty() {
+            self.add(subscriber, to_category);
+        } else if subscriber.replay(&events, self.isolation.as_mut(), self.poison.as_ref()) {
+            self.insert(subscriber, to_category);
+        }
+        subscription
+    }
+
+    fn add(&mut self, subscriber: SubscriberRef<T, E>, to_category: T) {
+        // A late subscriber is handed the category's sticky event first, and doesn't stay subscribed if that's all it wanted
+        if let Some(event) = self.sticky.get(&to_category) {
+            if !subscriber.replay(
+                slice::from_ref(event),
+                self.isolation.as_mut(),
+                self.poison.as_ref(),
+            ) {
+                return;
+            }
+        }
+        self.insert(subscriber, to_category);
+    }
+
+    fn insert(&mut self, subscriber: SubscriberRef<T, E>, to_category: T) {
+        self.remove_dropped();
         if let Some(subscriber_list) = self.channels.get_mut(&to_category) {
             // We have an existing subscriber list for this category, push a new subscriber to it
-            subscriber_list.push(Arc::downgrade(
-                &(subscriber.clone() as Arc<RwLock<dyn Subscriber<T, E> + 'static>>),
-            ));
+            subscriber_list.push(subscriber);
         } else {
             // No subscriber list exists yet for this category, create one
-            self.channels.insert(
-                to_category,
-                vec![Arc::downgrade(
-                    &(subscriber.clone() as Arc<RwLock<dyn Subscriber<T, E> + 'static>>),
-                )],
-            );
+            self.channels.insert(to_category, vec![subscriber]);
+        }
+    }
+
+    /// Removes the entries of every `Subscription` dropped since we last did, so they're gone before we next touch our subscriber lists
+    fn remove_dropped(&mut self) {
+        let dropped = self.dropped.take();
+        if dropped.is_empty() {
+            return;
         }
+        for subscriber_list in self.channels.values_mut() {
+            subscriber_list.retain(|subscriber| !dropped.contains(&subscriber.id()));
+        }
+        self.catch_all
+            .retain(|subscriber| !dropped.contains(&subscriber.id()));
     }
 
-    /// Unsubscribes the given `Subscriber` from the given category on this `EventBus` (non-blocking)
+    /// Unsubscribes the `Subscriber` with the given id (see `Subscription::id`) from the given category on this `EventBus` (non-blocking)
     ///
     /// ### Notes:
     /// - Automatically removes any dropped subscribers in the channel corresponding to the given category, if the bus encounters any.
-    /// - If a read-lock cannot be obtained, the subscriber will *NOT* be unsubscribed, as it cannot be identified without first obtaining a read-lock.
-    pub fn unsubscribe<S: Subscriber<T, E> + 'static>(&mut self, subscriber: &S, from_category: T) {
-        let mut cleanup_required = false;
+    ///
+    /// ### Returns
+    /// - `UnsubscribeOutcome`: whether the `Subscriber` was removed, wasn't subscribed to the given category, or had already been dropped.
+    pub fn unsubscribe(&mut self, id: SubscriberId, from_category: T) -> UnsubscribeOutcome {
+        self.remove_dropped();
+        let mut outcome = UnsubscribeOutcome::NotFound;
         if let Some(subscriber_list) = self.channels.get_mut(&from_category) {
-            if let Some(idx) = subscriber_list.iter().position(|weak_sub| {
-                if let Some(subscriber_arc) = weak_sub.upgrade() {
-                    match subscriber_arc.try_read() {
-                        Ok(sub) => sub.id() == subscriber.id(),
-                        Err(_) => false, // TODO: Look into more elegant handling, for now just skip
-                    }
+            if let Some(idx) = subscriber_list
+                .iter()
+                .position(|subscriber| subscriber.id() == id)
+            {
+                // Subscribers are notified in the order they subscribed in, so the rest of the list must keep its order
+                outcome = if subscriber_list.remove(idx).is_dropped() {
+                    UnsubscribeOutcome::AlreadyDead
                 } else {
-                    // We dropped a subscriber, need to clean up
-                    cleanup_required = true;
-                    false
-                }
-            }) {
-                // We can swap_remove for O(1) performance here because we don't care about ordering
-                subscriber_list.swap_remove(idx);
+                    UnsubscribeOutcome::Removed
+                };
             }
 
-            if cleanup_required {
-                subscriber_list.retain(|susbcriber| Weak::clone(susbcriber).upgrade().is_some());
+            if subscriber_list
+                .iter()
+                .any(|subscriber| subscriber.is_dropped())
+            {
+                subscriber_list.retain(|subscriber| !subscriber.is_dropped());
             }
         }
+        outcome
+    }
+
+    /// Unsubscribes a closure subscribed through `subscribe_fn` from this `EventBus`, dropping it (see `unsubscribe`)
+    pub fn unsubscribe_fn(&mut self, handle: &FnHandle<T>) -> UnsubscribeOutcome {
+        self.unsubscribe(handle.id, handle.subscribed_to.clone())
+    }
+
+    /// Unsubscribes the given `Subscriber` from every category on this `EventBus` it's subscribed to (including all of them at once, see `subscribe_all`), however many times it's subscribed to each
+    ///
+    /// ### Notes
+    /// - `subscriber` is the `Arc<RwLock<S>>` it was subscribed through, which is told apart from other `Subscriber`s without locking it.
+    ///
+    /// ### Returns
+    /// - `usize`: the number of subscriptions which were removed.
+    pub fn unsubscribe_everywhere<S: ?Sized>(&mut self, subscriber: &Arc<S>) -> usize {
+        self.remove_dropped();
+        let mut removed = 0;
+        for subscriber_list in self.channels.values_mut() {
+            let before = subscriber_list.len();
+            subscriber_list.retain(|entry| !entry.refers_to(subscriber));
+            removed += before - subscriber_list.len();
+        }
+        let before = self.catch_all.len();
+        self.catch_all.retain(|entry| !entry.refers_to(subscriber));
+        removed += before - self.catch_all.len();
+        removed
     }
 
     /// Removes all `Subscriber`s from this `EventBus`
@@ -101,41 +421,66 @@ where
     /// ### Notes
     /// - The memory previously allocated for the `Subscriber`s remains allocated for reuse.
     pub fn unsubscribe_all(&mut self) {
-        self.channels.clear()
+        self.remove_dropped();
+        self.channels.clear();
+        self.catch_all.clear();
     }
 
     /// Removes all `Subscriber`s from the given category on this `EventBus`
     pub fn unsubscribe_all_from_category(&mut self, from_category: T) {
+        self.remove_dropped();
         self.channels.remove(&from_category);
     }
 
-    /// Dispatches the given event to all `Subscriber`s of that event's category (non-blocking)
+    /// Marks the given category as sticky, so that the latest event dispatched to it is kept around and handed to every `Subscriber` which subscribes to it later on
     ///
-    /// Automatically removes any dropped `Subscriber`s in the channel the given event belongs to, if the bus encounters any.
-    pub fn dispatch_event(&mut self, event: &E) -> EventDispatchResult {
-        let mut result = EventDispatchResult::NotNeeded;
-        // Grab our list of subscribers for this event's category, if one exists
-        if let Some(subscriber_list) = self.channels.get_mut(&event.category()) {
-            let mut cleanup_required = false;
-            // Attempt to have all subscribers handle the dispatched event and return requests to the event bus (non-blocking)
-            result = execute_bus_requests(subscriber_list, |weak_subscriber| {
-                if let Some(subscriber_arc) = weak_subscriber.upgrade() {
-                    match subscriber_arc.try_read() {
-                        Ok(subscriber) => subscriber.on_event(event),
-                        Err(_) => BusRequest::DispatchFailed,
-                    }
-                } else {
-                    // Found an invalid reference to a subscriber (which was probably dropped by the owner)
-                    cleanup_required = true;
-                    BusRequest::NoActionNeeded
-                }
-            });
+    /// ### Notes
+    /// - An event is kept for every category it's routed to (see `hierarchical`), and events which are sticky themselves (see `Event::is_sticky`) are kept whatever their category.
+    /// - A `Subscriber` is handed the sticky event right away when it subscribes, and it isn't subscribed at all if it answers with `BusRequest::Unsubscribe`.
+    /// - A `Subscriber` which is locked elsewhere when it subscribes isn't handed the sticky event at all, as with a non-blocking dispatch.
+    /// - `Subscriber`s subscribed to every category (see `subscribe_all`) aren't handed sticky events.
+    pub fn make_sticky(&mut self, category: T) {
+        self.sticky_categories.insert(category);
+    }
+
+    /// The sticky event kept for the given category, if there is one (see `make_sticky`)
+    pub fn sticky_event(&self, category: &T) -> Option<&E> {
+        self.sticky.get(category)
+    }
+
+    /// Forgets the sticky event kept for the given category, if there is one, so late `Subscriber`s aren't handed it any longer
+    ///
+    /// ### Notes
+    /// - The category itself stays sticky, so the next event dispatched to it is kept again.
+    pub fn clear_sticky(&mut self, category: &T) -> Option<E> {
+        self.sticky.remove(category)
+    }
+
+    /// Forgets every sticky event, and stops keeping new ones for any category marked by `make_sticky`
+    pub fn clear_all_sticky(&mut self) {
+        self.sticky.clear();
+        self.sticky_categories.clear();
+    }
 
-            if cleanup_required {
-                subscriber_list.retain(|susbcriber| Weak::clone(susbcriber).upgrade().is_some());
+    /// Keeps the given event for each category it's routed to which is sticky, or for every one of them if the event itself is
+    fn keep_sticky(&mut self, event: &E) {
+        if self.sticky_categories.is_empty() && !event.is_sticky() {
+            return;
+        }
+        for category in route(&self.routing, event.categories()) {
+            if event.is_sticky() || self.sticky_categories.contains(&category) {
+                self.sticky.insert(category, event.clone());
             }
         }
+    }
 
+    /// Dispatches the given event to all `Subscriber`s of that event's category (non-blocking)
+    ///
+    /// Automatically removes any dropped `Subscriber`s in the channel the given event belongs to, if the bus encounters any.
+    pub fn dispatch_event(&mut self, event: &E) -> EventDispatchResult {
+        self.retry_automatically();
+        let result = self.dispatch(event, Wait::NonBlocking, None);
+        self.run_deferred(Wait::NonBlocking);
         result
     }
 
@@ -145,36 +490,317 @@ where
     /// - Automatically removes any dropped `Subscriber`s in the channel the given event belongs to, if the bus encounters any.
     /// - If a read-lock cannot be immediately obtained on a given subscriber, that specific subscriber will block the thread until it can be locked to receive the event.
     pub fn dispatch_blocking_event(&mut self, event: &E) -> EventDispatchResult {
-        let mut result = EventDispatchResult::NotNeeded;
-        // Grab the priority map for our category
-        if let Some(subscriber_list) = self.channels.get_mut(&event.category()) {
-            // Dispatch the event, automatically stopping propagation via `execute_bus_requests` if necessary
-            let mut cleanup_required = false;
-            result = execute_bus_requests(subscriber_list, |weak_subscriber| {
-                if let Some(subscriber_arc) = weak_subscriber.upgrade() {
-                    match subscriber_arc.read() {
-                        Ok(subscriber) => subscriber.on_event(event),
-                        Err(_) => BusRequest::DispatchFailed, // RwLock is poisoned
-                    }
-                } else {
-                    // Found an invalid reference to a subscriber (which was probably dropped by the owner)
-                    cleanup_required = true;
-                    BusRequest::NoActionNeeded
+        let result = self.dispatch(event, Wait::Blocking, None);
+        self.run_deferred(Wait::Blocking);
+        result
+    }
+
+    /// Dispatches the given event to all `Subscriber`s of that event's category (non-blocking), noting down what became of each of them along the way
+    ///
+    /// Automatically removes any dropped `Subscriber`s in the channel the given event belongs to, if the bus encounters any.
+    ///
+    /// If this `EventBus` retries busy `Subscriber`s automatically (see `retrying`), the report also accounts for the pending events retried ahead of this one, before the event itself.
+    pub fn dispatch_event_with_report(
+        &mut self,
+        event: &E,
+    ) -> (EventDispatchResult, DispatchReport) {
+        let retried = self.retry_automatically();
+        let mut report = DispatchReport::default();
+        let result = self.dispatch(event, Wait::NonBlocking, Some(&mut report));
+        self.run_deferred(Wait::NonBlocking);
+        match retried {
+            Some(mut retried) => {
+                retried.extend(report);
+                (result, retried)
+            }
+            None => (result, report),
+        }
+    }
+
+    /// Dispatches the given event to all `Subscriber`s of that event's category (blocking), noting down what became of each of them along the way
+    ///
+    /// ### Notes
+    /// - Automatically removes any dropped `Subscriber`s in the channel the given event belongs to, if the bus encounters any.
+    /// - If a read-lock cannot be immediately obtained on a given subscriber, that specific subscriber will block the thread until it can be locked to receive the event.
+    pub fn dispatch_blocking_event_with_report(
+        &mut self,
+        event: &E,
+    ) -> (EventDispatchResult, DispatchReport) {
+        let mut report = DispatchReport::default();
+        let result = self.dispatch(event, Wait::Blocking, Some(&mut report));
+        self.run_deferred(Wait::Blocking);
+        (result, report)
+    }
+
+    /// Dispatches the given event to all `Subscriber`s of that event's category, waiting on any which can't be locked right away until the given timeout has passed
+    ///
+    /// ### Notes
+    /// - Automatically removes any dropped `Subscriber`s in the channel the given event belongs to, if the bus encounters any.
+    /// - The timeout is a deadline for the whole dispatch (including any events published along the way, see `dispatch_queue`), so each `Subscriber` is only waited on for whatever is left of it.
+    /// - A `Subscriber` which still can't be locked once the deadline has passed doesn't receive the event, and counts as a failure (see `DispatchFailure::TimedOut`).
+    pub fn dispatch_event_timeout(&mut self, event: &E, timeout: Duration) -> EventDispatchResult {
+        let wait = Wait::Until(Instant::now() + timeout);
+        let result = self.dispatch(event, wait, None);
+        self.run_deferred(wait);
+        result
+    }
+
+    /// Dispatches the given event to all `Subscriber`s of that event's category, waiting on any which can't be locked right away until the given timeout has passed, noting down what became of each of them along the way
+    ///
+    /// ### Notes
+    /// - Automatically removes any dropped `Subscriber`s in the channel the given event belongs to, if the bus encounters any.
+    /// - Every `Subscriber` which timed out shows up in the report's `failed` list as `DispatchFailure::TimedOut`, apart from any whose lock was poisoned.
+    pub fn dispatch_event_timeout_with_report(
+        &mut self,
+        event: &E,
+        timeout: Duration,
+    ) -> (EventDispatchResult, DispatchReport) {
+        let mut report = DispatchReport::default();
+        let wait = Wait::Until(Instant::now() + timeout);
+        let result = self.dispatch(event, wait, Some(&mut report));
+        self.run_deferred(wait);
+        (result, report)
+    }
+
+    /// Retries every event which a non-blocking dispatch couldn't hand to a busy `Subscriber`, if this `EventBus` was set up through `retrying`
+    ///
+    /// ### Notes
+    /// - Each `Subscriber` is handed its pending events oldest first, until one of them finds it busy again, at which point the rest keep waiting for the next retry.
+    /// - An event which runs out of attempts is given up on, as are events waiting on a `Subscriber` which has been dropped or unsubscribed since.
+    /// - A `Subscriber` which unsubscribes while handling a retried event is only unsubscribed from the category that event came through, as it would've been had the event been handed over right away.
+    ///
+    /// ### Returns
+    /// - `DispatchReport`: what became of each retried event, with a `Subscriber` that's still busy showing up in `queued`, or as `DispatchFailure::LockBusy` in `failed` once the event has run out of attempts.
+    pub fn retry_pending(&mut self) -> DispatchReport {
+        self.remove_dropped();
+        let mut report = DispatchReport::default();
+        let mut unsubscribed = Vec::new();
+        if let Some(retries) = self.retries.as_mut() {
+            let (channels, catch_all) = (&self.channels, &self.catch_all);
+            let mut isolation = self.isolation.as_mut();
+            let poison = self.poison.as_ref();
+            unsubscribed = retries.retry(
+                |id, origin| {
+                    origin
+                        .map_or(Some(catch_all), |category| channels.get(category))
+                        .map_or(false, |subscriber_list| {
+                            subscriber_list
+                                .iter()
+                                .any(|subscriber| subscriber.id() == id)
+                        })
+                },
+                |subscriber, event| {
+                    subscriber.deliver_guarded(
+                        event,
+                        Wait::NonBlocking,
+                        isolation.as_deref_mut(),
+                        poison,
+                    )
+                },
+                &mut report,
+            );
+        }
+        // Subscribers asking to be unsubscribed while handling a retried event are only unsubscribed from the category it came through, just as they would've been had it been handed over right away
+        for (id, origin) in unsubscribed {
+            let subscriber_list = match origin {
+                Some(category) => self.channels.get_mut(&category),
+                None => Some(&mut self.catch_all),
+            };
+            if let Some(subscriber_list) = subscriber_list {
+                subscriber_list.retain(|subscriber| subscriber.id() != id);
+            }
+        }
+        self.run_deferred(Wait::NonBlocking);
+        report
+    }
+
+    /// The number of events waiting to be retried (see `retry_pending`), whichever `Subscriber` they're waiting on
+    pub fn pending_retries(&self) -> usize {
+        self.retries.as_ref().map_or(0, RetryQueue::len)
+    }
+
+    /// Retries every pending event if we're set up to do so ahead of each non-blocking dispatch, returning what became of them
+    fn retry_automatically(&mut self) -> Option<DispatchReport> {
+        if self
+            .retries
+            .as_ref()
+            .map_or(false, RetryQueue::is_automatic)
+        {
+            Some(self.retry_pending())
+        } else {
+            None
+        }
+    }
+
+    /// Queues the given event to be dispatched later on, by `drain` or `pump`, rather than right away
+    ///
+    /// ### Notes
+    /// - A `Subscriber` can't reach this bus while it's being dispatched to, so it queues events from inside `on_event` through its `DispatchQueue` instead (see `DispatchQueue::queue_event`).
+    pub fn queue_event(&mut self, event: E) {
+        self.queued.push_back(event);
+    }
+
+    /// The number of events queued by `queue_event` which are still waiting to be dispatched
+    pub fn queued_events(&self) -> usize {
+        self.queued.len()
+    }
+
+    /// Dispatches every queued event, in the order they were queued in
+    ///
+    /// ### Notes
+    /// - Events are dispatched as with `dispatch_blocking_event`.
+    /// - Automatically removes any dropped `Subscriber`s in the channels the queued events belong to, if the bus encounters any.
+    /// - Events queued along the way (see `DispatchQueue::queue_event`) are left queued for the next `drain` or `pump`.
+    ///
+    /// ### Returns
+    /// - `Vec<(E, EventDispatchResult)>`: every event that was dispatched, alongside the result of dispatching it.
+    pub fn drain(&mut self) -> Vec<(E, EventDispatchResult)> {
+        self.pump(self.queued.len())
+    }
+
+    /// Dispatches up to `max_events` of the queued events, in the order they were queued in, leaving the rest queued
+    ///
+    /// ### Notes
+    /// - Events are dispatched as with `dispatch_blocking_event`.
+    /// - Automatically removes any dropped `Subscriber`s in the channels the queued events belong to, if the bus encounters any.
+    /// - Events queued along the way (see `DispatchQueue::queue_event`) are left queued for the next `drain` or `pump`.
+    ///
+    /// ### Returns
+    /// - `Vec<(E, EventDispatchResult)>`: every event that was dispatched, alongside the result of dispatching it.
+    pub fn pump(&mut self, max_events: usize) -> Vec<(E, EventDispatchResult)> {
+        let count = max_events.min(self.queued.len());
+        let mut results = Vec::with_capacity(count);
+        for _ in 0..count {
+            if let Some(event) = self.queued.pop_front() {
+                let result = self.dispatch_blocking_event(&event);
+                results.push((event, result));
+            }
+        }
+        results
+    }
+
+    /// A handle onto this bus's queue of deferred commands, through which `Subscriber`s can publish events and change subscriptions from inside `on_event`
+    ///
+    /// ### Notes
+    /// - Commands queued while dispatching an event are run once this bus has finished dispatching it, in the order they were queued in.
+    pub fn dispatch_queue(&self) -> DispatchQueue<Self, E> {
+        self.deferred.clone()
+    }
+
+    /// Runs every command deferred by our subscribers, including any deferred along the way, until there are none left
+    ///
+    /// Events published along the way are dispatched the same way (non-blocking, blocking or up to the same deadline) as the event they followed up on
+    fn run_deferred(&mut self, wait: Wait) {
+        while let Some(command) = self.deferred.pop() {
+            match command {
+                Command::Publish(event) => {
+                    self.dispatch(&event, wait, None);
                 }
-            });
-            if cleanup_required {
-                subscriber_list.retain(|subscriber| Weak::clone(subscriber).upgrade().is_some());
+                Command::Apply(command) => command(self),
+            }
+        }
+    }
+
+    /// Dispatches the given event through `dispatch_to_subscribers`, handing it to our dead-letter sink afterwards if it didn't reach a single subscriber, and recording it in our history
+    fn dispatch(
+        &mut self,
+        event: &E,
+        wait: Wait,
+        report: Option<&mut DispatchReport>,
+    ) -> EventDispatchResult {
+        self.remove_dropped();
+        self.keep_sticky(event);
+        let result = if self.dead_letters.is_none() {
+            self.dispatch_to_subscribers(event, wait, report)
+        } else {
+            // Whether the event is a dead letter is told by what became of it, so we need a report of our own if we weren't asked for one
+            let mut own_report = DispatchReport::default();
+            let report = report.unwrap_or(&mut own_report);
+            let result = self.dispatch_to_subscribers(event, wait, Some(&mut *report));
+            if let (Some(sink), Some(reason)) = (&self.dead_letters, report.dead_letter()) {
+                sink(event, reason);
             }
+            result
+        };
+        if let Some(history) = self.history.as_mut() {
+            history.record(route(&self.routing, event.categories()), event, &result);
         }
         result
     }
+
+    fn dispatch_to_subscribers(
+        &mut self,
+        event: &E,
+        wait: Wait,
+        mut report: Option<&mut DispatchReport>,
+    ) -> EventDispatchResult {
+        let mut handed = Handed::default();
+        let mut outcome = None;
+        let mut retries = match wait {
+            Wait::NonBlocking => self.retries.as_mut(),
+            Wait::Blocking | Wait::Until(_) => None,
+        };
+        let mut isolation = self.isolation.as_mut();
+        let poison = self.poison.as_ref();
+        // Busy subscribers have the event queued up to be retried, if we're set up to retry them at all
+        let mut deliver = |subscriber: &SubscriberRef<T, E>, origin: Option<&T>| {
+            let isolation = isolation.as_deref_mut();
+            match retries.as_deref_mut() {
+                Some(retries) => retries.deliver(subscriber, origin, event, || {
+                    subscriber.deliver_guarded(event, wait, isolation, poison)
+                }),
+                None => subscriber.deliver_guarded(event, wait, isolation, poison),
+            }
+        };
+        // Grab our list of subscribers for each category the event is routed to, if one exists, until propagation is halted
+        for category in route(&self.routing, event.categories()) {
+            if let Some(subscriber_list) = self.channels.get_mut(&category) {
+                // Have all subscribers handle the dispatched event and return requests to the event bus
+                if let Some((stopped, failures)) =
+                    execute_bus_requests(subscriber_list, report.as_deref_mut(), |subscriber| {
+                        handed.deliver(subscriber.address(), || {
+                            deliver(subscriber, Some(&category))
+                        })
+                    })
+                {
+                    let (_, earlier_failures) = outcome.unwrap_or((false, 0));
+                    outcome = Some((stopped, earlier_failures + failures));
+                    if stopped {
+                        // Whoever was handed the event in this category mustn't be handed it again by the catch-all pass below
+                        handed.next_category();
+                        break;
+                    }
+                }
+                handed.next_category();
+            }
+        }
+        // Subscribers of every category see the event even if propagation was halted before them
+        if !self.catch_all.is_empty() {
+            if let Some((_, failures)) =
+                execute_bus_requests(&mut self.catch_all, report, |subscriber| {
+                    handed.deliver(subscriber.address(), || deliver(subscriber, None))
+                })
+            {
+                let (stopped, earlier_failures) = outcome.unwrap_or((false, 0));
+                outcome = Some((stopped, earlier_failures + failures));
+            }
+        }
+        match outcome {
+            Some((stopped, failures)) => dispatch_result(stopped, failures),
+            None => EventDispatchResult::NotNeeded,
+        }
+    }
 }
 
 /// Single-thread datastructure responsible for dispatching events from `Publisher`s to `Subscriber`s in a prioritized order
 ///
 /// This keeps the respective Pub/Sub systems decoupled from each other
 ///
-/// This should be wrapped in a Rc<RefCell<PriorityEventBus>>
+/// `Subscriber`s subscribed to every category (see `subscribe_all`) are notified after every priority segment
+///
+/// A bus created through `hierarchical` also hands each event to the `Subscriber`s of every ancestor of its category (see `HierarchicalCategory`)
+///
+/// This should be wrapped in a Arc<RwLock<PriorityEventBus>>
 pub struct PriorityEventBus<T, E, P>
 where
     T: Eq + PartialEq + Hash + Clone + Send + Sync + 'static,
@@ -182,6 +808,22 @@ where
     P: Ord,
 {
     channels: PrioritySubscriberMap<T, E, P>,
+    // Subscribers handed every event, whatever its category (see `subscribe_all`)
+    catch_all: Vec<SubscriberRef<T, E>>,
+    // How events are routed through the ancestors of their category, if this bus was created through `hierarchical`
+    routing: Option<Routing<T>>,
+    // How subscribers which panic are dealt with, if this bus was set up through `isolating_panics`
+    isolation: Option<Isolation>,
+    // How subscribers with poisoned locks are dealt with, if this bus was set up through `handling_poison`
+    poison: Option<PoisonPolicy>,
+    // Where events which don't reach a single subscriber are handed, if this bus was set up through `collecting_dead_letters`
+    dead_letters: Option<DeadLetterSink<E>>,
+    // The latest events dispatched to each category, if this bus was set up through `recording_history`
+    history: Option<History<T, E, EventDispatchResult>>,
+    // The ids of `Subscription`s dropped since we last removed their entries (see `remove_dropped`)
+    dropped: DroppedSubscriptions,
+    // Commands deferred by subscribers until we've finished dispatching the current event
+    deferred: DispatchQueue<Self, E>,
 }
 
 impl<T, E, P> Default for PriorityEventBus<T, E, P>
@@ -193,6 +835,14 @@ where
     fn default() -> Self {
         Self {
             channels: HashMap::default(),
+            catch_all: Vec::new(),
+            routing: None,
+            isolation: None,
+            poison: None,
+            dead_letters: None,
+            history: None,
+            dropped: DroppedSubscriptions::default(),
+            deferred: DispatchQueue::default(),
         }
     }
 }
@@ -203,84 +853,337 @@ where
     E: Event<T> + Eq + PartialEq + Hash + Clone + Send + Sync + 'static,
     P: Ord,
 {
+    /// Creates a `PriorityEventBus` which also hands each event to the `Subscriber`s of every ancestor of its category, in the given order
+    ///
+    /// ### Notes
+    /// - Every priority segment of one category is dispatched to before any of the next category's.
+    pub fn hierarchical(order: RoutingOrder) -> Self
+    where
+        T: HierarchicalCategory,
+    {
+        Self {
+            routing: Some(Routing::new(order)),
+            ..Self::default()
+        }
+    }
+
+    /// Has this `PriorityEventBus` catch any panic a `Subscriber` throws while handling an event, rather than letting it unwind through the whole dispatch, and deal with that `Subscriber` as the given policy says (see `PanicPolicy`)
+    pub fn isolating_panics(mut self, policy: PanicPolicy) -> Self {
+        self.isolation = Some(Isolation::new(policy));
+        self
+    }
+
+    /// Has this `PriorityEventBus` deal with `Subscriber`s whose lock was poisoned by an earlier panic as the given policy says, rather than failing to hand each of them every event from then on (see `PoisonPolicy`)
+    pub fn handling_poison(mut self, policy: PoisonPolicy) -> Self {
+        self.poison = Some(policy);
+        self
+    }
+
+    /// The id of every `Subscriber` quarantined after panicking (see `PanicPolicy::Quarantine`), in the order they were quarantined in
+    pub fn quarantined(&self) -> Vec<SubscriberId> {
+        self.isolation
+            .as_ref()
+            .map_or_else(Vec::new, Isolation::quarantined)
+    }
+
+    /// Lets the quarantined `Subscriber` with the given id be handed events again
+    ///
+    /// ### Returns
+    /// - `bool`: whether the `Subscriber` was quarantined at all.
+    pub fn release(&mut self, id: SubscriberId) -> bool {
+        self.isolation
+            .as_mut()
+            .map_or(false, |isolation| isolation.release(id))
+    }
+
+    /// Has this `PriorityEventBus` hand every event which doesn't reach a single `Subscriber` to the given sink, along with why it didn't (see `DeadLetter`)
+    pub fn collecting_dead_letters<F: Fn(&E, DeadLetter) + Send + Sync + 'static>(
+        mut self,
+        sink: F,
+    ) -> Self {
+        self.dead_letters = Some(Box::new(sink));
+        self
+    }
+
+    /// Has this `PriorityEventBus` keep the latest `capacity` events dispatched to each category, along with what became of them (see `HistoryEntry`)
+    pub fn recording_history(mut self, capacity: usize) -> Self {
+        self.history = Some(History::new(capacity));
+        self
+    }
+
+    /// The latest events dispatched to the given category, oldest first, if this `PriorityEventBus` was set up through `recording_history`
+    pub fn history(&self, category: &T) -> Vec<&HistoryEntry<E, EventDispatchResult>> {
+        self.history
+            .as_ref()
+            .map_or_else(Vec::new, |history| history.entries(category))
+    }
+
     /// Adds the given `Subscriber` to a prioritized subscriber list to receive published messages of the given event category
+    ///
+    /// ### Returns
+    /// - `Subscription`: a guard which unsubscribes the `Subscriber` once it's dropped, call `detach` on it to keep the `Subscriber` subscribed instead.
     pub fn subscribe<S: Subscriber<T, E> + 'static>(
         &mut self,
         subscriber: &Arc<RwLock<S>>,
         to_category: T,
         with_priority: P,
-    ) {
-        if let Some(category_priority_map) = self.channels.get_mut(&to_category) {
-            if let Some(subscriber_list) = category_priority_map.get_mut(&with_priority) {
-                // We have an existing subscriber list for this category, push a new subscriber to it
-                subscriber_list.push(Arc::downgrade(
-                    &(subscriber.clone() as Arc<RwLock<dyn Subscriber<T, E> + 'static>>),
-                ));
-            } else {
-                // No subscriber list exists yet for this priority segment in this category, create one
-                category_priority_map.insert(
-                    with_priority,
-                    vec![Arc::downgrade(
-                        &(subscriber.clone() as Arc<RwLock<dyn Subscriber<T, E> + 'static>>),
-                    )],
-                );
-            }
-        } else {
-            // This category doesn't exist yet, create it
-            let mut priority_map = BTreeMap::default();
-            priority_map.insert(
-                with_priority,
-                vec![Arc::downgrade(
-                    &(subscriber.clone() as Arc<RwLock<dyn Subscriber<T, E> + 'static>>),
-                )],
-            );
-            self.channels.insert(to_category, priority_map);
-        }
+    ) -> Subscription {
+        let subscriber = SubscriberRef::new(
+            Arc::downgrade(&(subscriber.clone() as Arc<RwLock<dyn Subscriber<T, E> + 'static>>)),
+            false,
+        );
+        let subscription = subscriber.subscription(&self.dropped);
+        self.add(subscriber, to_category, with_priority);
+        subscription
     }
 
-    /// Unsubscribes the given `Subscriber` from the given priority segment in the given category from this `PriorityEventBus` (non-blocking)
+    /// Adds the given `Subscriber` to a prioritized subscriber list to receive published messages of the given event category through `on_event_mut`, under a write-lock
     ///
-    /// ### Notes
-    /// - This method drills down to the provided category and priority segment within that category directly to locate and unsubscribe a `Subscriber`.
+    /// ### Returns
+    /// - `Subscription`: a guard which unsubscribes the `Subscriber` once it's dropped, call `detach` on it to keep the `Subscriber` subscribed instead.
+    pub fn subscribe_mut<S: Subscriber<T, E> + 'static>(
+        &mut self,
+        subscriber: &Arc<RwLock<S>>,
+        to_category: T,
+        with_priority: P,
+    ) -> Subscription {
+        let subscriber = SubscriberRef::new(
+            Arc::downgrade(&(subscriber.clone() as Arc<RwLock<dyn Subscriber<T, E> + 'static>>)),
+            true,
+        );
+        let subscription = subscriber.subscription(&self.dropped);
+        self.add(subscriber, to_category, with_priority);
+        subscription
+    }
+
+    /// Adds the given `Subscriber` to a prioritized subscriber list to receive published messages of the given event category, but only those matching the given predicate
+    ///
+    /// ### Returns
+    /// - `Subscription`: a guard which unsubscribes the `Subscriber` once it's dropped, call `detach` on it to keep the `Subscriber` subscribed instead.
+    ///
+    /// ### Notes
+    /// - The predicate is checked before the `Subscriber` is handed each event, and an event it rejects is never delivered: it shows up in a `DispatchReport`'s `filtered` rather than its `received`, and doesn't count towards the dispatch result.
+    pub fn subscribe_filtered<
+        S: Subscriber<T, E> + 'static,
+        F: Fn(&E) -> bool + Send + Sync + 'static,
+    >(
+        &mut self,
+        subscriber: &Arc<RwLock<S>>,
+        to_category: T,
+        with_priority: P,
+        predicate: F,
+    ) -> Subscription {
+        let subscriber = SubscriberRef::new(
+            Arc::downgrade(&(subscriber.clone() as Arc<RwLock<dyn Subscriber<T, E> + 'static>>)),
+            false,
+        )
+        .filtered_by(predicate);
+        let subscription = subscriber.subscription(&self.dropped);
+        self.add(subscriber, to_category, with_priority);
+        subscription
+    }
+
+    /// Adds the given `Subscriber` to the prioritized subscriber lists of each of the given event categories in the given priority segment at once
+    ///
+    /// ### Returns
+    /// - `Subscription`: a single guard for every one of those subscriptions, which unsubscribes the `Subscriber` from all of them once it's dropped. They all share its id, so `unsubscribe` still unsubscribes the `Subscriber` from one category at a time.
+    pub fn subscribe_many<S: Subscriber<T, E> + 'static, I: IntoIterator<Item = T>>(
+        &mut self,
+        subscriber: &Arc<RwLock<S>>,
+        to_categories: I,
+        with_priority: P,
+    ) -> Subscription
+    where
+        P: Clone,
+    {
+        let subscriber = SubscriberRef::new(
+            Arc::downgrade(&(subscriber.clone() as Arc<RwLock<dyn Subscriber<T, E> + 'static>>)),
+            false,
+        );
+        let subscription = subscriber.subscription(&self.dropped);
+        for to_category in to_categories {
+            self.add(subscriber.clone(), to_category, with_priority.clone());
+        }
+        subscription
+    }
+
+    /// Adds the given `Subscriber` to receive every event published on this `PriorityEventBus`, whatever its category
+    ///
+    /// ### Notes
+    /// - It's notified after every priority segment of the event's category, even if one of those halted propagation. Halting propagation itself only keeps the event from the rest of the `Subscriber`s subscribed to every category.
+    /// - Unlike any other `Subscriber`, it isn't subscribed to a category it can be unsubscribed from, so it stays subscribed until its `Subscription` is dropped, `unsubscribe_everywhere` is called for it, or it's dropped.
+    ///
+    /// ### Returns
+    /// - `Subscription`: a guard which unsubscribes the `Subscriber` once it's dropped, call `detach` on it to keep the `Subscriber` subscribed instead.
+    pub fn subscribe_all<S: Subscriber<T, E> + 'static>(
+        &mut self,
+        subscriber: &Arc<RwLock<S>>,
+    ) -> Subscription {
+        self.remove_dropped();
+        let subscriber = SubscriberRef::new(
+            Arc::downgrade(&(subscriber.clone() as Arc<RwLock<dyn Subscriber<T, E> + 'static>>)),
+            false,
+        );
+        let subscription = subscriber.subscription(&self.dropped);
+        self.catch_all.push(subscriber);
+        subscription
+    }
+
+    /// Subscribes the given closure to a prioritized subscriber list to receive published messages of the given event category, with this `PriorityEventBus` taking ownership of it
+    ///
+    /// ### Returns
+    /// - `FnHandle<(T, P)>`: a handle which can be handed to `unsubscribe_fn` to unsubscribe the closure again.
+    pub fn subscribe_fn<F: Fn(&E) -> BusRequest + Send + Sync + 'static>(
+        &mut self,
+        to_category: T,
+        with_priority: P,
+        function: F,
+    ) -> FnHandle<(T, P)>
+    where
+        P: Clone,
+    {
+        let subscriber = SubscriberRef::owned(Arc::new(RwLock::new(FnSubscriber::new(function))));
+        let handle = FnHandle {
+            id: subscriber.id(),
+            subscribed_to: (to_category.clone(), with_priority.clone()),
+        };
+        self.add(subscriber, to_category, with_priority);
+        handle
+    }
+
+    /// Adds the given `Subscriber` to a prioritized subscriber list to receive published messages of the given event category, handing it the latest `last` events dispatched to that category first, oldest first (see `HistoryEntry`)
+    ///
+    /// ### Returns
+    /// - `Subscription`: a guard which unsubscribes the `Subscriber` once it's dropped, call `detach` on it to keep the `Subscriber` subscribed instead.
+    pub fn subscribe_with_replay<S: Subscriber<T, E> + 'static>(
+        &mut self,
+        subscriber: &Arc<RwLock<S>>,
+        to_category: T,
+        with_priority: P,
+        last: usize,
+    ) -> Subscription {
+        let subscriber = SubscriberRef::new(
+            Arc::downgrade(&(subscriber.clone() as Arc<RwLock<dyn Subscriber<T, E> + 'static>>)),
+            false,
+        );
+        let subscription = subscriber.subscription(&self.dropped);
+        let events = self.history.as_ref().map_or_else(Vec::new, |history| {
+            history.latest(|category| *category == to_category, last)
+        });
+        if subscriber.replay(&events, self.isolation.as_mut(), self.poison.as_ref()) {
+            self.add(subscriber, to_category, with_priority);
+        }
+        subscription
+    }
+
+    fn add(&mut self, subscriber: SubscriberRef<T, E>, to_category: T, with_priority: P) {
+        self.remove_dropped();
+        if let Some(category_priority_map) = self.channels.get_mut(&to_category) {
+            if let Some(subscriber_list) = category_priority_map.get_mut(&with_priority) {
+                // We have an existing subscriber list for this category, push a new subscriber to it
+                subscriber_list.push(subscriber);
+            } else {
+                // No subscriber list exists yet for this priority segment in this category, create one
+                category_priority_map.insert(with_priority, vec![subscriber]);
+            }
+        } else {
+            // This category doesn't exist yet, create it
+            let mut priority_map = BTreeMap::default();
+            priority_map.insert(with_priority, vec![subscriber]);
+            self.channels.insert(to_category, priority_map);
+        }
+    }
+
+    /// Removes the entries of every `Subscription` dropped since we last did, so they're gone before we next touch our subscriber lists
+    fn remove_dropped(&mut self) {
+        let dropped = self.dropped.take();
+        if dropped.is_empty() {
+            return;
+        }
+        for category_priority_map in self.channels.values_mut() {
+            for subscriber_list in category_priority_map.values_mut() {
+                subscriber_list.retain(|subscriber| !dropped.contains(&subscriber.id()));
+            }
+        }
+        self.catch_all
+            .retain(|subscriber| !dropped.contains(&subscriber.id()));
+    }
+
+    /// Unsubscribes the `Subscriber` with the given id (see `Subscription::id`) from the given priority segment in the given category from this `PriorityEventBus` (non-blocking)
+    ///
+    /// ### Notes
+    /// - This method drills down to the provided category and priority segment within that category directly to locate and unsubscribe a `Subscriber`.
     /// - This method automatically removes any dropped subscribers it encounters during the search.
-    /// - If a read-lock cannot be obtained, the subscriber will not be unsubscribed, as it cannot be identified without a read-lock.
     ///
     /// ### Returns
-    /// - `bool`: `true` if the subscriber was successfully unsubscribed, `false` if it was not (for various reasons, including not found).
-    pub fn unsubscribe<S: Subscriber<T, E> + 'static>(
+    /// - `UnsubscribeOutcome`: whether the `Subscriber` was removed, wasn't subscribed to the given priority segment, or had already been dropped.
+    pub fn unsubscribe(
         &mut self,
-        subscriber: &S,
+        id: SubscriberId,
         from_category: &T,
         with_priority: &P,
-    ) -> bool {
-        // Grab our priority map
+    ) -> UnsubscribeOutcome {
+        self.remove_dropped();
+        // Grab our priority map, then the subscriber list to unsubscribe from
+        self.channels
+            .get_mut(from_category)
+            .and_then(|category_priority_map| category_priority_map.get_mut(with_priority))
+            .map_or(UnsubscribeOutcome::NotFound, |subscriber_list| {
+                unsubscribe_from_segment(subscriber_list, id)
+            })
+    }
+
+    /// Unsubscribes a closure subscribed through `subscribe_fn` from this `PriorityEventBus`, dropping it (see `unsubscribe`)
+    pub fn unsubscribe_fn(&mut self, handle: &FnHandle<(T, P)>) -> UnsubscribeOutcome {
+        let (from_category, with_priority) = &handle.subscribed_to;
+        self.unsubscribe(handle.id, from_category, with_priority)
+    }
+
+    /// Unsubscribes the given `Subscriber` from every category on this `PriorityEventBus` it's subscribed to (including all of them at once, see `subscribe_all`), however many times it's subscribed to each
+    ///
+    /// ### Notes
+    /// - `subscriber` is the `Arc<RwLock<S>>` it was subscribed through, which is told apart from other `Subscriber`s without locking it.
+    ///
+    /// ### Returns
+    /// - `usize`: the number of subscriptions which were removed.
+    pub fn unsubscribe_everywhere<S: ?Sized>(&mut self, subscriber: &Arc<S>) -> usize {
+        self.remove_dropped();
+        let mut removed = 0;
+        for category_priority_map in self.channels.values_mut() {
+            for subscriber_list in category_priority_map.values_mut() {
+                let before = subscriber_list.len();
+                subscriber_list.retain(|entry| !entry.refers_to(subscriber));
+                removed += before - subscriber_list.len();
+            }
+        }
+        let before = self.catch_all.len();
+        self.catch_all.retain(|entry| !entry.refers_to(subscriber));
+        removed += before - self.catch_all.len();
+        removed
+    }
+
+    /// Unsubscribes the `Subscriber` with the given id (see `Subscription::id`) from the given category on this `PriorityEventBus`, whichever priority segment it's in (non-blocking)
+    ///
+    /// ### Notes
+    /// - This method automatically removes any dropped subscribers it encounters during the search.
+    ///
+    /// ### Returns
+    /// - `UnsubscribeOutcome`: whether the `Subscriber` was removed, wasn't subscribed to the given category, or had already been dropped.
+    pub fn unsubscribe_from_category(
+        &mut self,
+        id: SubscriberId,
+        from_category: &T,
+    ) -> UnsubscribeOutcome {
+        self.remove_dropped();
         if let Some(category_priority_map) = self.channels.get_mut(from_category) {
-            // Grab the subscriber list and find the index of the subscriber to unsubscribe
-            if let Some(subscriber_list) = category_priority_map.get_mut(with_priority) {
-                let mut cleanup_required = false;
-                if let Some(idx) = subscriber_list.iter().position(|weak_sub| {
-                    if let Some(sub_arc) = weak_sub.upgrade() {
-                        match sub_arc.try_read() {
-                            Ok(sub) => sub.id() == subscriber.id(),
-                            Err(_) => false, //  TODO: More elegant handling for this, for now just skip
-                        }
-                    } else {
-                        // Found an invalid reference to a subscriber (which was probably dropped by the owner)
-                        cleanup_required = true;
-                        false
-                    }
-                }) {
-                    // We can swap_remove here because the subscribers are pooled by priority, and the ordering of two subscribers with the same priority doesn't matter
-                    subscriber_list.swap_remove(idx);
-                    return true;
-                }
-                if cleanup_required {
-                    subscriber_list
-                        .retain(|susbcriber| Weak::clone(susbcriber).upgrade().is_some());
+            for subscriber_list in category_priority_map.values_mut() {
+                match unsubscribe_from_segment(subscriber_list, id) {
+                    UnsubscribeOutcome::NotFound => continue,
+                    outcome => return outcome,
                 }
             }
         }
-        false
+        UnsubscribeOutcome::NotFound
     }
 
     /// Removes all `Subscriber`s from this `PriorityEventBus`
@@ -288,11 +1191,14 @@ where
     /// ### Notes
     /// - The memory previously allocated for the `Subscriber`s remains allocated for reuse.
     pub fn unsubscribe_all(&mut self) {
-        self.channels.clear()
+        self.remove_dropped();
+        self.channels.clear();
+        self.catch_all.clear();
     }
 
     /// Removes all `Subscriber`s from the given category on this `PriorityEventBus`
     pub fn unsubscribe_all_from_category(&mut self, from_category: &T) {
+        self.remove_dropped();
         self.channels.remove(from_category);
     }
 
@@ -302,6 +1208,7 @@ where
         from_category: &T,
         with_priority: &P,
     ) {
+        self.remove_dropped();
         if let Some(category_priority_map) = self.channels.get_mut(from_category) {
             category_priority_map.remove(with_priority);
         }
@@ -311,33 +1218,21 @@ where
     ///
     /// ### Notes
     /// - Automatically removes any dropped `Subscriber`s in the channel the given event belongs to, if the bus encounters any.
+    /// - Priority segments are dispatched to in ascending order of `P`, and a `Subscriber` requesting `DoNotPropagate` halts dispatch to every segment after its own.
     /// - If a read-lock cannot be obtained on a given subscriber, that specific subscriber will not receive the event.
     pub fn dispatch_event(&mut self, event: &E) -> EventDispatchResult {
-        let mut result = EventDispatchResult::NotNeeded;
-        // Grab the priority map for our category
-        if let Some(category_priority_map) = self.channels.get_mut(&event.category()) {
-            // For each distinct priority segment, in order of priority
-            for subscriber_list in category_priority_map.values_mut() {
-                // Dispatch the event, automatically stopping propagation via `execute_bus_requests` if necessary
-                let mut cleanup_required = false;
-                result = execute_bus_requests(subscriber_list, |weak_subscriber| {
-                    if let Some(subscriber_arc) = weak_subscriber.upgrade() {
-                        match subscriber_arc.try_read() {
-                            Ok(subscriber) => subscriber.on_event(event),
-                            Err(_) => BusRequest::DispatchFailed, // Couldn't obtain lock, or the RwLock is poisoned
-                        }
-                    } else {
-                        // Found an invalid reference to a subscriber (which was probably dropped by the owner)
-                        cleanup_required = true;
-                        BusRequest::NoActionNeeded
-                    }
-                });
-                if cleanup_required {
-                    subscriber_list
-                        .retain(|subscriber| Weak::clone(subscriber).upgrade().is_some());
-                }
-            }
-        }
+        let result = self.dispatch(event, Wait::NonBlocking, None, |_| ());
+        self.run_deferred(Wait::NonBlocking);
+        result.into()
+    }
+
+    /// Dispatches the given event to all `Subscriber`s of that event's category (non-blocking), as `dispatch_event` does, telling which priority segment halted propagation (if any did) along with the failures summed across every segment
+    pub fn dispatch_event_with_stop(&mut self, event: &E) -> PriorityDispatchResult<P>
+    where
+        P: Clone,
+    {
+        let result = self.dispatch(event, Wait::NonBlocking, None, P::clone);
+        self.run_deferred(Wait::NonBlocking);
         result
     }
 
@@ -345,37 +1240,2198 @@ where
     ///
     /// ### Notes
     /// - Automatically removes any dropped `Subscriber`s in the channel the given event belongs to, if the bus encounters any.
+    /// - Priority segments are dispatched to in ascending order of `P`, and a `Subscriber` requesting `DoNotPropagate` halts dispatch to every segment after its own.
     /// - If a read-lock cannot be immediately obtained on a given subscriber, that specific subscriber will block the thread until it can be locked to receive the event.
     pub fn dispatch_blocking_event(&mut self, event: &E) -> EventDispatchResult {
-        let mut result = EventDispatchResult::NotNeeded;
-        // Grab the priority map for our category
-        if let Some(category_priority_map) = self.channels.get_mut(&event.category()) {
-            // For each distinct priority segment, in order of priority
-            for subscriber_list in category_priority_map.values_mut() {
-                // Dispatch the event, automatically stopping propagation via `execute_bus_requests` if necessary
-                let mut cleanup_required = false;
-                result = execute_bus_requests(subscriber_list, |weak_subscriber| {
-                    if let Some(subscriber_arc) = weak_subscriber.upgrade() {
-                        match subscriber_arc.read() {
-                            Ok(subscriber) => subscriber.on_event(event),
-                            Err(_) => BusRequest::DispatchFailed, // RwLock is poisoned
-                        }
-                    } else {
-                        // Found an invalid reference to a subscriber (which was probably dropped by the owner)
-                        cleanup_required = true;
-                        BusRequest::NoActionNeeded
-                    }
-                });
-                if cleanup_required {
-                    subscriber_list
-                        .retain(|subscriber| Weak::clone(subscriber).upgrade().is_some());
+        let result = self.dispatch(event, Wait::Blocking, None, |_| ());
+        self.run_deferred(Wait::Blocking);
+        result.into()
+    }
+
+    /// Dispatches the given event to all `Subscriber`s of that event's category (blocking), as `dispatch_blocking_event` does, telling which priority segment halted propagation (if any did) along with the failures summed across every segment
+    pub fn dispatch_blocking_event_with_stop(&mut self, event: &E) -> PriorityDispatchResult<P>
+    where
+        P: Clone,
+    {
+        let result = self.dispatch(event, Wait::Blocking, None, P::clone);
+        self.run_deferred(Wait::Blocking);
+        result
+    }
+
+    /// Dispatches the given event to all `Subscriber`s of that event's category (non-blocking), noting down what became of each of them along the way
+    ///
+    /// ### Notes
+    /// - Automatically removes any dropped `Subscriber`s in the channel the given event belongs to, if the bus encounters any.
+    /// - Priority segments are dispatched to in ascending order of `P`, and a `Subscriber` requesting `DoNotPropagate` halts dispatch to every segment after its own.
+    /// - If a read-lock cannot be obtained on a given subscriber, that specific subscriber will not receive the event.
+    pub fn dispatch_event_with_report(
+        &mut self,
+        event: &E,
+    ) -> (EventDispatchResult, DispatchReport) {
+        let mut report = DispatchReport::default();
+        let result = self.dispatch(event, Wait::NonBlocking, Some(&mut report), |_| ());
+        self.run_deferred(Wait::NonBlocking);
+        (result.into(), report)
+    }
+
+    /// Dispatches the given event to all `Subscriber`s of that event's category (blocking), noting down what became of each of them along the way
+    ///
+    /// ### Notes
+    /// - Automatically removes any dropped `Subscriber`s in the channel the given event belongs to, if the bus encounters any.
+    /// - Priority segments are dispatched to in ascending order of `P`, and a `Subscriber` requesting `DoNotPropagate` halts dispatch to every segment after its own.
+    /// - If a read-lock cannot be immediately obtained on a given subscriber, that specific subscriber will block the thread until it can be locked to receive the event.
+    pub fn dispatch_blocking_event_with_report(
+        &mut self,
+        event: &E,
+    ) -> (EventDispatchResult, DispatchReport) {
+        let mut report = DispatchReport::default();
+        let result = self.dispatch(event, Wait::Blocking, Some(&mut report), |_| ());
+        self.run_deferred(Wait::Blocking);
+        (result.into(), report)
+    }
+
+    /// Dispatches the given event to all `Subscriber`s of that event's category, waiting on any which can't be locked right away until the given timeout has passed
+    ///
+    /// ### Notes
+    /// - Automatically removes any dropped `Subscriber`s in the channel the given event belongs to, if the bus encounters any.
+    /// - Priority segments are dispatched to in ascending order of `P`, and a `Subscriber` requesting `DoNotPropagate` halts dispatch to every segment after its own.
+    /// - The timeout is a deadline for the whole dispatch (including any events published along the way, see `dispatch_queue`), so each `Subscriber` is only waited on for whatever is left of it.
+    /// - A `Subscriber` which still can't be locked once the deadline has passed doesn't receive the event, and counts as a failure (see `DispatchFailure::TimedOut`).
+    pub fn dispatch_event_timeout(&mut self, event: &E, timeout: Duration) -> EventDispatchResult {
+        let wait = Wait::Until(Instant::now() + timeout);
+        let result = self.dispatch(event, wait, None, |_| ());
+        self.run_deferred(wait);
+        result.into()
+    }
+
+    /// Dispatches the given event to all `Subscriber`s of that event's category, waiting on any which can't be locked right away until the given timeout has passed, noting down what became of each of them along the way
+    ///
+    /// ### Notes
+    /// - Automatically removes any dropped `Subscriber`s in the channel the given event belongs to, if the bus encounters any.
+    /// - Priority segments are dispatched to in ascending order of `P`, and a `Subscriber` requesting `DoNotPropagate` halts dispatch to every segment after its own.
+    /// - Every `Subscriber` which timed out shows up in the report's `failed` list as `DispatchFailure::TimedOut`, apart from any whose lock was poisoned.
+    pub fn dispatch_event_timeout_with_report(
+        &mut self,
+        event: &E,
+        timeout: Duration,
+    ) -> (EventDispatchResult, DispatchReport) {
+        let mut report = DispatchReport::default();
+        let wait = Wait::Until(Instant::now() + timeout);
+        let result = self.dispatch(event, wait, Some(&mut report), |_| ());
+        self.run_deferred(wait);
+        (result.into(), report)
+    }
+
+    /// A handle onto this bus's queue of deferred commands, through which `Subscriber`s can publish events and change subscriptions from inside `on_event`
+    ///
+    /// ### Notes
+    /// - Commands queued while dispatching an event are run once this bus has finished dispatching it, in the order they were queued in.
+    pub fn dispatch_queue(&self) -> DispatchQueue<Self, E> {
+        self.deferred.clone()
+    }
+
+    /// Runs every command deferred by our subscribers, including any deferred along the way, until there are none left
+    ///
+    /// Events published along the way are dispatched the same way (non-blocking, blocking or up to the same deadline) as the event they followed up on
+    fn run_deferred(&mut self, wait: Wait) {
+        while let Some(command) = self.deferred.pop() {
+            match command {
+                Command::Publish(event) => {
+                    self.dispatch(&event, wait, None, |_| ());
                 }
+                Command::Apply(command) => command(self),
+            }
+        }
+    }
+
+    /// Dispatches the given event through `dispatch_to_subscribers`, handing it to our dead-letter sink afterwards if it didn't reach a single subscriber, and recording it in our history
+    ///
+    /// `stop` notes down the priority segment which halted propagation, if any did
+    fn dispatch<S: Clone>(
+        &mut self,
+        event: &E,
+        wait: Wait,
+        report: Option<&mut DispatchReport>,
+        stop: fn(&P) -> S,
+    ) -> PriorityDispatchResult<S> {
+        self.remove_dropped();
+        let result = if self.dead_letters.is_none() {
+            self.dispatch_to_subscribers(event, wait, report, stop)
+        } else {
+            // Whether the event is a dead letter is told by what became of it, so we need a report of our own if we weren't asked for one
+            let mut own_report = DispatchReport::default();
+            let report = report.unwrap_or(&mut own_report);
+            let result = self.dispatch_to_subscribers(event, wait, Some(&mut *report), stop);
+            if let (Some(sink), Some(reason)) = (&self.dead_letters, report.dead_letter()) {
+                sink(event, reason);
             }
+            result
+        };
+        if let Some(history) = self.history.as_mut() {
+            let flattened = EventDispatchResult::from(result.clone());
+            history.record(route(&self.routing, event.categories()), event, &flattened);
         }
         result
     }
+
+    fn dispatch_to_subscribers<S>(
+        &mut self,
+        event: &E,
+        wait: Wait,
+        mut report: Option<&mut DispatchReport>,
+        stop: fn(&P) -> S,
+    ) -> PriorityDispatchResult<S> {
+        let mut dispatched = false;
+        let mut stopped_at = None;
+        let mut failures = 0;
+        let mut handed = Handed::default();
+        let mut isolation = self.isolation.as_mut();
+        let poison = self.poison.as_ref();
+        // Grab the priority map for each category the event is routed to, if one exists
+        'route: for category in route(&self.routing, event.categories()) {
+            if let Some(category_priority_map) = self.channels.get_mut(&category) {
+                // For each distinct priority segment, in order of priority, until propagation is halted
+                for (priority, subscriber_list) in category_priority_map.iter_mut() {
+                    // Dispatch the event, automatically stopping propagation via `execute_bus_requests` if necessary
+                    let (stopped, segment_failures) = match execute_bus_requests(
+                        subscriber_list,
+                        report.as_deref_mut(),
+                        |subscriber| {
+                            handed.deliver(subscriber.address(), || {
+                                subscriber.deliver_guarded(
+                                    event,
+                                    wait,
+                                    isolation.as_deref_mut(),
+                                    poison,
+                                )
+                            })
+                        },
+                    ) {
+                        Some(outcome) => outcome,
+                        // Every subscriber in this segment filtered the event out
+                        None => continue,
+                    };
+                    dispatched = true;
+                    // Failures are summed across segments, so a later segment which finished cleanly can't hide those of an earlier one
+                    failures += segment_failures;
+                    if stopped {
+                        // Propagation was halted, lower priority segments (and later categories) must not see the event
+                        stopped_at = Some(stop(priority));
+                        // Whoever was handed the event in this category mustn't be handed it again by the catch-all pass below
+                        handed.next_category();
+                        break 'route;
+                    }
+                }
+                handed.next_category();
+            }
+        }
+        // Subscribers of every category see the event even if propagation was halted before them
+        if !self.catch_all.is_empty() {
+            if let Some((_, catch_all_failures)) =
+                execute_bus_requests(&mut self.catch_all, report, |subscriber| {
+                    handed.deliver(subscriber.address(), || {
+                        subscriber.deliver_guarded(event, wait, isolation.as_deref_mut(), poison)
+                    })
+                })
+            {
+                dispatched = true;
+                failures += catch_all_failures;
+            }
+        }
+        if dispatched {
+            priority_dispatch_result(stopped_at, failures)
+        } else {
+            PriorityDispatchResult::NotNeeded
+        }
+    }
+}
+
+/// Removes the `Subscriber` with the given id from the given priority segment, along with any dropped subscribers in it
+fn unsubscribe_from_segment<T, E>(
+    subscriber_list: &mut Vec<SubscriberRef<T, E>>,
+    id: SubscriberId,
+) -> UnsubscribeOutcome
+where
+    T: Eq + PartialEq + Hash + Clone + Send + Sync + 'static,
+    E: Event<T> + Eq + PartialEq + Hash + Clone + Send + Sync + 'static,
+{
+    let mut outcome = UnsubscribeOutcome::NotFound;
+    // Find the index of the subscriber to unsubscribe
+    if let Some(idx) = subscriber_list
+        .iter()
+        .position(|subscriber| subscriber.id() == id)
+    {
+        // Subscribers within a priority segment are notified in the order they subscribed in, so the rest of the segment must keep its order
+        outcome = if subscriber_list.remove(idx).is_dropped() {
+            UnsubscribeOutcome::AlreadyDead
+        } else {
+            UnsubscribeOutcome::Removed
+        };
+    }
+    if subscriber_list
+        .iter()
+        .any(|subscriber| subscriber.is_dropped())
+    {
+        // Found invalid references to subscribers (which were probably dropped by their owners)
+        subscriber_list.retain(|subscriber| !subscriber.is_dropped());
+    }
+    outcome
+}
+
+/// Thread-safe datastructure responsible for dispatching events from `Publisher`s to `Subscriber`s by topic
+///
+/// This keeps the respective Pub/Sub systems decoupled from each other
+///
+/// An event's category is a `/`-separated topic such as `sensors/kitchen/temperature`, which `Subscriber`s subscribe to through MQTT-style topic filters:
+/// - `+` matches any single level of a topic, i.e. `sensors/+/temperature`.
+/// - `#`, as a filter's last level, matches whatever is left of a topic (including nothing at all), i.e. `sensors/#`.
+///
+/// `Subscriber`s are notified filter by filter: at each level of the topic, `Subscriber`s of filters naming that level come first, followed by those of filters with a `+` there, followed by those of filters ending in a `#` there. `Subscriber`s of the same filter are notified in the order they subscribed in.
+///
+/// A `Subscriber` of several filters matching the same topic (i.e. `sensors/+` and `sensors/#`) is only handed each event once, through the first of them.
+///
+/// This should be wrapped in a Arc<RwLock<TopicEventBus>>
+pub struct TopicEventBus<E>
+where
+    E: Event<String> + Eq + PartialEq + Hash + Clone + Send + Sync + 'static,
+{
+    // Subscriber lists are kept by topic filter, level by level, so a topic can be matched against every filter at once
+    channels: TopicTrie<SubscriberRef<String, E>>,
+    // How subscribers which panic are dealt with, if this bus was set up through `isolating_panics`
+    isolation: Option<Isolation>,
+    // How subscribers with poisoned locks are dealt with, if this bus was set up through `handling_poison`
+    poison: Option<PoisonPolicy>,
+    // Where events which don't reach a single subscriber are handed, if this bus was set up through `collecting_dead_letters`
+    dead_letters: Option<DeadLetterSink<E>>,
+    // The latest events dispatched to each topic, if this bus was set up through `recording_history`
+    history: Option<History<String, E, EventDispatchResult>>,
+    // The ids of `Subscription`s dropped since we last removed their entries (see `remove_dropped`)
+    dropped: DroppedSubscriptions,
+    // Commands deferred by subscribers until we've finished dispatching the current event
+    deferred: DispatchQueue<Self, E>,
+}
+
+impl<E> Default for TopicEventBus<E>
+where
+    E: Event<String> + Eq + PartialEq + Hash + Clone + Send + Sync + 'static,
+{
+    fn default() -> Self {
+        Self {
+            channels: TopicTrie::default(),
+            isolation: None,
+            poison: None,
+            dead_letters: None,
+            history: None,
+            dropped: DroppedSubscriptions::default(),
+            deferred: DispatchQueue::default(),
+        }
+    }
 }
 
-// TODO: Add ParallelEventBus
+impl<E> TopicEventBus<E>
+where
+    E: Event<String> + Eq + PartialEq + Hash + Clone + Send + Sync + 'static,
+{
+    /// Has this `TopicEventBus` catch any panic a `Subscriber` throws while handling an event, rather than letting it unwind through the whole dispatch, and deal with that `Subscriber` as the given policy says (see `PanicPolicy`)
+    pub fn isolating_panics(mut self, policy: PanicPolicy) -> Self {
+        self.isolation = Some(Isolation::new(policy));
+        self
+    }
+
+    /// Has this `TopicEventBus` deal with `Subscriber`s whose lock was poisoned by an earlier panic as the given policy says, rather than failing to hand each of them every event from then on (see `PoisonPolicy`)
+    pub fn handling_poison(mut self, policy: PoisonPolicy) -> Self {
+        self.poison = Some(policy);
+        self
+    }
+
+    /// The id of every `Subscriber` quarantined after panicking (see `PanicPolicy::Quarantine`), in the order they were quarantined in
+    pub fn quarantined(&self) -> Vec<SubscriberId> {
+        self.isolation
+            .as_ref()
+            .map_or_else(Vec::new, Isolation::quarantined)
+    }
+
+    /// Lets the quarantined `Subscriber` with the given id be handed events again
+    ///
+    /// ### Returns
+    /// - `bool`: whether the `Subscriber` was quarantined at all.
+    pub fn release(&mut self, id: SubscriberId) -> bool {
+        self.isolation
+            .as_mut()
+            .map_or(false, |isolation| isolation.release(id))
+    }
+
+    /// Has this `TopicEventBus` hand every event which doesn't reach a single `Subscriber` to the given sink, along with why it didn't (see `DeadLetter`)
+    pub fn collecting_dead_letters<F: Fn(&E, DeadLetter) + Send + Sync + 'static>(
+        mut self,
+        sink: F,
+    ) -> Self {
+        self.dead_letters = Some(Box::new(sink));
+        self
+    }
+
+    /// Has this `TopicEventBus` keep the latest `capacity` events dispatched to each topic, along with what became of them (see `HistoryEntry`)
+    pub fn recording_history(mut self, capacity: usize) -> Self {
+        self.history = Some(History::new(capacity));
+        self
+    }
+
+    /// The latest events dispatched to the given topic, oldest first, if this `TopicEventBus` was set up through `recording_history`
+    pub fn history(&self, topic: &str) -> Vec<&HistoryEntry<E, EventDispatchResult>> {
+        self.history
+            .as_ref()
+            .map_or_else(Vec::new, |history| history.entries(topic))
+    }
+
+    /// Adds the given `Subscriber` to a subscriber list to receive published messages of every topic matching the given topic filter
+    ///
+    /// ### Returns
+    /// - `Ok(Subscription)`: a guard which unsubscribes the `Subscriber` once it's dropped, call `detach` on it to keep the `Subscriber` subscribed instead.
+    /// - `Err(TopicFilterError)`: the filter had a wildcard somewhere it isn't allowed, so the `Subscriber` wasn't subscribed.
+    pub fn subscribe<S: Subscriber<String, E> + 'static>(
+        &mut self,
+        subscriber: &Arc<RwLock<S>>,
+        to_filter: &str,
+    ) -> Result<Subscription, TopicFilterError> {
+        let subscriber = SubscriberRef::new(
+            Arc::downgrade(
+                &(subscriber.clone() as Arc<RwLock<dyn Subscriber<String, E> + 'static>>),
+            ),
+            false,
+        );
+        let subscription = subscriber.subscription(&self.dropped);
+        self.add(subscriber, to_filter)?;
+        Ok(subscription)
+    }
+
+    /// Adds the given `Subscriber` to a subscriber list to receive published messages of every topic matching the given topic filter through `on_event_mut`, under a write-lock
+    ///
+    /// ### Returns
+    /// - `Ok(Subscription)`: a guard which unsubscribes the `Subscriber` once it's dropped, call `detach` on it to keep the `Subscriber` subscribed instead.
+    /// - `Err(TopicFilterError)`: the filter had a wildcard somewhere it isn't allowed, so the `Subscriber` wasn't subscribed.
+    pub fn subscribe_mut<S: Subscriber<String, E> + 'static>(
+        &mut self,
+        subscriber: &Arc<RwLock<S>>,
+        to_filter: &str,
+    ) -> Result<Subscription, TopicFilterError> {
+        let subscriber = SubscriberRef::new(
+            Arc::downgrade(
+                &(subscriber.clone() as Arc<RwLock<dyn Subscriber<String, E> + 'static>>),
+            ),
+            true,
+        );
+        let subscription = subscriber.subscription(&self.dropped);
+        self.add(subscriber, to_filter)?;
+        Ok(subscription)
+    }
+
+    /// Adds the given `Subscriber` to a subscriber list to receive published messages of every topic matching the given topic filter, but only those matching the given predicate
+    ///
+    /// ### Returns
+    /// - `Ok(Subscription)`: a guard which unsubscribes the `Subscriber` once it's dropped, call `detach` on it to keep the `Subscriber` subscribed instead.
+    /// - `Err(TopicFilterError)`: the filter had a wildcard somewhere it isn't allowed, so the `Subscriber` wasn't subscribed.
+    ///
+    /// ### Notes
+    /// - The predicate is checked before the `Subscriber` is handed each event, and an event it rejects is never delivered: it shows up in a `DispatchReport`'s `filtered` rather than its `received`, and doesn't count towards the dispatch result.
+    pub fn subscribe_filtered<
+        S: Subscriber<String, E> + 'static,
+        F: Fn(&E) -> bool + Send + Sync + 'static,
+    >(
+        &mut self,
+        subscriber: &Arc<RwLock<S>>,
+        to_filter: &str,
+        predicate: F,
+    ) -> Result<Subscription, TopicFilterError> {
+        let subscriber = SubscriberRef::new(
+            Arc::downgrade(
+                &(subscriber.clone() as Arc<RwLock<dyn Subscriber<String, E> + 'static>>),
+            ),
+            false,
+        )
+        .filtered_by(predicate);
+        let subscription = subscriber.subscription(&self.dropped);
+        self.add(subscriber, to_filter)?;
+        Ok(subscription)
+    }
+
+    /// Subscribes the given closure to receive published messages of every topic matching the given topic filter, with this `TopicEventBus` taking ownership of it
+    ///
+    /// ### Returns
+    /// - `Ok(FnHandle<String>)`: a handle which can be handed to `unsubscribe_fn` to unsubscribe the closure again.
+    /// - `Err(TopicFilterError)`: the filter had a wildcard somewhere it isn't allowed, so the closure wasn't subscribed.
+    pub fn subscribe_fn<F: Fn(&E) -> BusRequest + Send + Sync + 'static>(
+        &mut self,
+        to_filter: &str,
+        function: F,
+    ) -> Result<FnHandle<String>, TopicFilterError> {
+        let subscriber = SubscriberRef::owned(Arc::new(RwLock::new(FnSubscriber::new(function))));
+        let handle = FnHandle {
+            id: subscriber.id(),
+            subscribed_to: to_filter.to_string(),
+        };
+        self.add(subscriber, to_filter)?;
+        Ok(handle)
+    }
+
+    /// Adds the given `Subscriber` to a subscriber list to receive published messages of every topic matching the given topic filter, handing it the latest `last` events dispatched to any of those topics first, oldest first (see `HistoryEntry`)
+    ///
+    /// ### Returns
+    /// - `Ok(Subscription)`: a guard which unsubscribes the `Subscriber` once it's dropped, call `detach` on it to keep the `Subscriber` subscribed instead.
+    /// - `Err(TopicFilterError)`: the filter had a wildcard somewhere it isn't allowed, so the `Subscriber` wasn't subscribed (nor handed any events).
+    pub fn subscribe_with_replay<S: Subscriber<String, E> + 'static>(
+        &mut self,
+        subscriber: &Arc<RwLock<S>>,
+        to_filter: &str,
+        last: usize,
+    ) -> Result<Subscription, TopicFilterError> {
+        let filter = parse_filter(to_filter)?;
+        let subscriber = SubscriberRef::new(
+            Arc::downgrade(
+                &(subscriber.clone() as Arc<RwLock<dyn Subscriber<String, E> + 'static>>),
+            ),
+            false,
+        );
+        let subscription = subscriber.subscription(&self.dropped);
+        let events = self.history.as_ref().map_or_else(Vec::new, |history| {
+            history.latest(|topic| filter_matches(&filter, topic), last)
+        });
+        if subscriber.replay(&events, self.isolation.as_mut(), self.poison.as_ref()) {
+            self.remove_dropped();
+            self.channels.list_mut(&filter).push(subscriber);
+        }
+        Ok(subscription)
+    }
+
+    fn add(
+        &mut self,
+        subscriber: SubscriberRef<String, E>,
+        to_filter: &str,
+    ) -> Result<(), TopicFilterError> {
+        self.remove_dropped();
+        let filter = parse_filter(to_filter)?;
+        self.channels.list_mut(&filter).push(subscriber);
+        Ok(())
+    }
 
-// TODO: Add testing
+    /// Removes the entries of every `Subscription` dropped since we last did, so they're gone before we next touch our subscriber lists
+    fn remove_dropped(&mut self) {
+        let dropped = self.dropped.take();
+        if dropped.is_empty() {
+            return;
+        }
+        for subscriber_list in self.channels.lists_mut() {
+            subscriber_list.retain(|subscriber| !dropped.contains(&subscriber.id()));
+        }
+        self.channels.prune();
+    }
+
+    /// Unsubscribes the `Subscriber` with the given id (see `Subscription::id`) from the given topic filter on this `TopicEventBus` (non-blocking)
+    ///
+    /// ### Notes
+    /// - `from_filter` has to be the filter the `Subscriber` subscribed to, not a topic it matches.
+    /// - Automatically removes any dropped subscribers of the given topic filter, if the bus encounters any.
+    ///
+    /// ### Returns
+    /// - `UnsubscribeOutcome`: whether the `Subscriber` was removed, wasn't subscribed to the given topic filter, or had already been dropped.
+    pub fn unsubscribe(&mut self, id: SubscriberId, from_filter: &str) -> UnsubscribeOutcome {
+        self.remove_dropped();
+        let mut outcome = UnsubscribeOutcome::NotFound;
+        let filter = match parse_filter(from_filter) {
+            Ok(filter) => filter,
+            Err(_) => return outcome,
+        };
+        if let Some(subscriber_list) = self.channels.find_mut(&filter) {
+            if let Some(idx) = subscriber_list
+                .iter()
+                .position(|subscriber| subscriber.id() == id)
+            {
+                // Subscribers are notified in the order they subscribed in, so the rest of the list must keep its order
+                outcome = if subscriber_list.remove(idx).is_dropped() {
+                    UnsubscribeOutcome::AlreadyDead
+                } else {
+                    UnsubscribeOutcome::Removed
+                };
+            }
+
+            if subscriber_list
+                .iter()
+                .any(|subscriber| subscriber.is_dropped())
+            {
+                subscriber_list.retain(|subscriber| !subscriber.is_dropped());
+            }
+        }
+        self.channels.prune();
+        outcome
+    }
+
+    /// Unsubscribes a closure subscribed through `subscribe_fn` from this `TopicEventBus`, dropping it (see `unsubscribe`)
+    pub fn unsubscribe_fn(&mut self, handle: &FnHandle<String>) -> UnsubscribeOutcome {
+        self.unsubscribe(handle.id, &handle.subscribed_to)
+    }
+
+    /// Unsubscribes the given `Subscriber` from every topic filter on this `TopicEventBus` it's subscribed to, however many times it's subscribed to each
+    ///
+    /// ### Notes
+    /// - `subscriber` is the `Arc<RwLock<S>>` it was subscribed through, which is told apart from other `Subscriber`s without locking it.
+    ///
+    /// ### Returns
+    /// - `usize`: the number of subscriptions which were removed.
+    pub fn unsubscribe_everywhere<S: ?Sized>(&mut self, subscriber: &Arc<S>) -> usize {
+        self.remove_dropped();
+        let mut removed = 0;
+        for subscriber_list in self.channels.lists_mut() {
+            let before = subscriber_list.len();
+            subscriber_list.retain(|entry| !entry.refers_to(subscriber));
+            removed += before - subscriber_list.len();
+        }
+        self.channels.prune();
+        removed
+    }
+
+    /// Removes all `Subscriber`s from this `TopicEventBus`
+    pub fn unsubscribe_all(&mut self) {
+        self.remove_dropped();
+        self.channels.clear();
+    }
+
+    /// Removes all `Subscriber`s from the given topic filter on this `TopicEventBus`
+    ///
+    /// ### Notes
+    /// - `from_filter` is a filter, not a topic, so `Subscriber`s of other filters matching the same topics stay subscribed.
+    pub fn unsubscribe_all_from_filter(&mut self, from_filter: &str) {
+        self.remove_dropped();
+        if let Ok(filter) = parse_filter(from_filter) {
+            if let Some(subscriber_list) = self.channels.find_mut(&filter) {
+                subscriber_list.clear();
+            }
+            self.channels.prune();
+        }
+    }
+
+    /// Dispatches the given event to all `Subscriber`s of every topic filter matching that event's topic (non-blocking)
+    ///
+    /// ### Notes
+    /// - Automatically removes any dropped `Subscriber`s of the topic filters the given event's topic matches, if the bus encounters any.
+    /// - A `Subscriber` requesting `DoNotPropagate` halts dispatch to every topic filter after its own.
+    pub fn dispatch_event(&mut self, event: &E) -> EventDispatchResult {
+        let result = self.dispatch(event, Wait::NonBlocking, None);
+        self.run_deferred(Wait::NonBlocking);
+        result
+    }
+
+    /// Dispatches the given event to all `Subscriber`s of every topic filter matching that event's topic (blocking)
+    ///
+    /// ### Notes
+    /// - Automatically removes any dropped `Subscriber`s of the topic filters the given event's topic matches, if the bus encounters any.
+    /// - A `Subscriber` requesting `DoNotPropagate` halts dispatch to every topic filter after its own.
+    /// - If a read-lock cannot be immediately obtained on a given subscriber, that specific subscriber will block the thread until it can be locked to receive the event.
+    pub fn dispatch_blocking_event(&mut self, event: &E) -> EventDispatchResult {
+        let result = self.dispatch(event, Wait::Blocking, None);
+        self.run_deferred(Wait::Blocking);
+        result
+    }
+
+    /// Dispatches the given event to all `Subscriber`s of every topic filter matching that event's topic (non-blocking), noting down what became of each of them along the way
+    ///
+    /// ### Notes
+    /// - Automatically removes any dropped `Subscriber`s of the topic filters the given event's topic matches, if the bus encounters any.
+    /// - A `Subscriber` requesting `DoNotPropagate` halts dispatch to every topic filter after its own.
+    pub fn dispatch_event_with_report(
+        &mut self,
+        event: &E,
+    ) -> (EventDispatchResult, DispatchReport) {
+        let mut report = DispatchReport::default();
+        let result = self.dispatch(event, Wait::NonBlocking, Some(&mut report));
+        self.run_deferred(Wait::NonBlocking);
+        (result, report)
+    }
+
+    /// Dispatches the given event to all `Subscriber`s of every topic filter matching that event's topic (blocking), noting down what became of each of them along the way
+    ///
+    /// ### Notes
+    /// - Automatically removes any dropped `Subscriber`s of the topic filters the given event's topic matches, if the bus encounters any.
+    /// - A `Subscriber` requesting `DoNotPropagate` halts dispatch to every topic filter after its own.
+    /// - If a read-lock cannot be immediately obtained on a given subscriber, that specific subscriber will block the thread until it can be locked to receive the event.
+    pub fn dispatch_blocking_event_with_report(
+        &mut self,
+        event: &E,
+    ) -> (EventDispatchResult, DispatchReport) {
+        let mut report = DispatchReport::default();
+        let result = self.dispatch(event, Wait::Blocking, Some(&mut report));
+        self.run_deferred(Wait::Blocking);
+        (result, report)
+    }
+
+    /// A handle onto this bus's queue of deferred commands, through which `Subscriber`s can publish events and change subscriptions from inside `on_event`
+    ///
+    /// ### Notes
+    /// - Commands queued while dispatching an event are run once this bus has finished dispatching it, in the order they were queued in.
+    pub fn dispatch_queue(&self) -> DispatchQueue<Self, E> {
+        self.deferred.clone()
+    }
+
+    /// Runs every command deferred by our subscribers, including any deferred along the way, until there are none left
+    ///
+    /// Events published along the way are dispatched the same way (non-blocking, blocking or up to the same deadline) as the event they followed up on
+    fn run_deferred(&mut self, wait: Wait) {
+        while let Some(command) = self.deferred.pop() {
+            match command {
+                Command::Publish(event) => {
+                    self.dispatch(&event, wait, None);
+                }
+                Command::Apply(command) => command(self),
+            }
+        }
+    }
+
+    /// Dispatches the given event through `dispatch_to_subscribers`, handing it to our dead-letter sink afterwards if it didn't reach a single subscriber, and recording it in our history
+    fn dispatch(
+        &mut self,
+        event: &E,
+        wait: Wait,
+        report: Option<&mut DispatchReport>,
+    ) -> EventDispatchResult {
+        self.remove_dropped();
+        let result = if self.dead_letters.is_none() {
+            self.dispatch_to_subscribers(event, wait, report)
+        } else {
+            // Whether the event is a dead letter is told by what became of it, so we need a report of our own if we weren't asked for one
+            let mut own_report = DispatchReport::default();
+            let report = report.unwrap_or(&mut own_report);
+            let result = self.dispatch_to_subscribers(event, wait, Some(&mut *report));
+            if let (Some(sink), Some(reason)) = (&self.dead_letters, report.dead_letter()) {
+                sink(event, reason);
+            }
+            result
+        };
+        if let Some(history) = self.history.as_mut() {
+            history.record(distinct(event.categories()), event, &result);
+        }
+        result
+    }
+
+    fn dispatch_to_subscribers(
+        &mut self,
+        event: &E,
+        wait: Wait,
+        mut report: Option<&mut DispatchReport>,
+    ) -> EventDispatchResult {
+        let mut handed = Handed::default();
+        let mut isolation = self.isolation.as_mut();
+        let poison = self.poison.as_ref();
+        let mut outcome = None;
+        let mut emptied = false;
+        // Have the subscribers of every filter matching each of the event's topics handle the dispatched event in turn, until propagation is halted
+        for topic in distinct(event.categories()) {
+            let levels: Vec<&str> = topic.split('/').collect();
+            let stopped = self
+                .channels
+                .visit_matching(&levels, &mut |subscriber_list| {
+                    let requests = execute_bus_requests(
+                        subscriber_list,
+                        report.as_deref_mut(),
+                        |subscriber| {
+                            handed.deliver(subscriber.address(), || {
+                                subscriber.deliver_guarded(
+                                    event,
+                                    wait,
+                                    isolation.as_deref_mut(),
+                                    poison,
+                                )
+                            })
+                        },
+                    );
+                    emptied |= subscriber_list.is_empty();
+                    // Each filter counts as a category of its own, so a subscriber of overlapping filters is only handed the event once
+                    handed.next_category();
+                    match requests {
+                        Some((stopped, failures)) => {
+                            let (_, earlier_failures) = outcome.unwrap_or((false, 0));
+                            outcome = Some((stopped, earlier_failures + failures));
+                            stopped
+                        }
+                        None => false,
+                    }
+                });
+            if stopped {
+                break;
+            }
+        }
+        // Subscribers removed along the way may have left levels of the trie which no longer lead to any
+        if emptied {
+            self.channels.prune();
+        }
+        match outcome {
+            Some((stopped, failures)) => dispatch_result(stopped, failures),
+            None => EventDispatchResult::NotNeeded,
+        }
+    }
+}
+
+/// Thread-safe datastructure responsible for dispatching events from `Publisher`s to `Subscriber`s, handing each event to every `Subscriber` of its category at the same time on a pool of worker threads
+///
+/// This keeps the respective Pub/Sub systems decoupled from each other
+///
+/// A bus created through `hierarchical` also hands each event to the `Subscriber`s of every ancestor of its category (see `HierarchicalCategory`), one category at a time
+///
+/// This should be wrapped in a Arc<RwLock<ParallelEventBus>>
+pub struct ParallelEventBus<T, E>
+where
+    T: Eq + PartialEq + Hash + Clone + Send + Sync + 'static,
+    E: Event<T> + Eq + PartialEq + Hash + Clone + Send + Sync + 'static,
+{
+    // Subscriptions are managed exactly the same way as on an `EventBus`, only the way we dispatch differs
+    bus: EventBus<T, E>,
+    pool: WorkerPool,
+    // Commands deferred by subscribers until we've finished dispatching the current event
+    deferred: DispatchQueue<Self, E>,
+}
+
+impl<T, E> Default for ParallelEventBus<T, E>
+where
+    T: Eq + PartialEq + Hash + Clone + Send + Sync + 'static,
+    E: Event<T> + Eq + PartialEq + Hash + Clone + Send + Sync + 'static,
+{
+    /// Creates a `ParallelEventBus` with one worker thread per available CPU
+    fn default() -> Self {
+        Self::new(
+            thread::available_parallelism()
+                .map(NonZeroUsize::get)
+                .unwrap_or(1),
+        )
+    }
+}
+
+impl<T, E> ParallelEventBus<T, E>
+where
+    T: Eq + PartialEq + Hash + Clone + Send + Sync + 'static,
+    E: Event<T> + Eq + PartialEq + Hash + Clone + Send + Sync + 'static,
+{
+    /// Creates a `ParallelEventBus` which dispatches events on a pool of `workers` threads (at least one)
+    pub fn new(workers: usize) -> Self {
+        Self {
+            bus: EventBus::default(),
+            pool: WorkerPool::new(workers),
+            deferred: DispatchQueue::default(),
+        }
+    }
+
+    /// Creates a `ParallelEventBus` which dispatches events on a pool of `workers` threads (at least one), also handing each event to the `Subscriber`s of every ancestor of its category, in the given order
+    ///
+    /// ### Notes
+    /// - Every `Subscriber` of one category has handled the event before any of the next category's are handed it.
+    pub fn hierarchical(workers: usize, order: RoutingOrder) -> Self
+    where
+        T: HierarchicalCategory,
+    {
+        Self {
+            bus: EventBus::hierarchical(order),
+            ..Self::new(workers)
+        }
+    }
+
+    /// Has this `ParallelEventBus` catch any panic a `Subscriber` throws while handling an event on its worker thread, and deal with that `Subscriber` as the given policy says (see `PanicPolicy`)
+    pub fn isolating_panics(mut self, policy: PanicPolicy) -> Self {
+        self.bus.isolation = Some(Isolation::new(policy));
+        self
+    }
+
+    /// Has this `ParallelEventBus` deal with `Subscriber`s whose lock was poisoned by an earlier panic as the given policy says (see `PoisonPolicy`)
+    pub fn handling_poison(mut self, policy: PoisonPolicy) -> Self {
+        self.bus.poison = Some(policy);
+        self
+    }
+
+    /// The id of every `Subscriber` quarantined after panicking (see `PanicPolicy::Quarantine`), in the order they were quarantined in
+    pub fn quarantined(&self) -> Vec<SubscriberId> {
+        self.bus.quarantined()
+    }
+
+    /// Lets the quarantined `Subscriber` with the given id be handed events again (see `EventBus::release`)
+    pub fn release(&mut self, id: SubscriberId) -> bool {
+        self.bus.release(id)
+    }
+
+    /// Has this `ParallelEventBus` hand every event which doesn't reach a single `Subscriber` to the given sink, along with why it didn't (see `DeadLetter`)
+    pub fn collecting_dead_letters<F: Fn(&E, DeadLetter) + Send + Sync + 'static>(
+        mut self,
+        sink: F,
+    ) -> Self {
+        self.bus.dead_letters = Some(Box::new(sink));
+        self
+    }
+
+    /// Has this `ParallelEventBus` keep the latest `capacity` events dispatched to each category, along with what became of them (see `HistoryEntry`)
+    pub fn recording_history(mut self, capacity: usize) -> Self {
+        self.bus.history = Some(History::new(capacity));
+        self
+    }
+
+    /// The latest events dispatched to the given category, oldest first, if this `ParallelEventBus` was set up through `recording_history`
+    pub fn history(&self, category: &T) -> Vec<&HistoryEntry<E, EventDispatchResult>> {
+        self.bus.history(category)
+    }
+
+    /// The number of worker threads this `ParallelEventBus` dispatches events on
+    pub fn workers(&self) -> usize {
+        self.pool.size()
+    }
+
+    /// Adds the given `Subscriber` to a subscriber list to receive published messages of the given event category
+    ///
+    /// ### Returns
+    /// - `Subscription`: a guard which unsubscribes the `Subscriber` once it's dropped, call `detach` on it to keep the `Subscriber` subscribed instead.
+    pub fn subscribe<S: Subscriber<T, E> + 'static>(
+        &mut self,
+        subscriber: &Arc<RwLock<S>>,
+        to_category: T,
+    ) -> Subscription {
+        self.bus.subscribe(subscriber, to_category)
+    }
+
+    /// Adds the given `Subscriber` to a subscriber list to receive published messages of the given event category, handing it the latest `last` events dispatched to that category first, oldest first (see `HistoryEntry`)
+    ///
+    /// ### Returns
+    /// - `Subscription`: a guard which unsubscribes the `Subscriber` once it's dropped, call `detach` on it to keep the `Subscriber` subscribed instead.
+    pub fn subscribe_with_replay<S: Subscriber<T, E> + 'static>(
+        &mut self,
+        subscriber: &Arc<RwLock<S>>,
+        to_category: T,
+        last: usize,
+    ) -> Subscription {
+        self.bus
+            .subscribe_with_replay(subscriber, to_category, last)
+    }
+
+    /// Adds the given `Subscriber` to a subscriber list to receive published messages of the given event category through `on_event_mut`, under a write-lock
+    ///
+    /// ### Returns
+    /// - `Subscription`: a guard which unsubscribes the `Subscriber` once it's dropped, call `detach` on it to keep the `Subscriber` subscribed instead.
+    pub fn subscribe_mut<S: Subscriber<T, E> + 'static>(
+        &mut self,
+        subscriber: &Arc<RwLock<S>>,
+        to_category: T,
+    ) -> Subscription {
+        self.bus.subscribe_mut(subscriber, to_category)
+    }
+
+    /// Adds the given `Subscriber` to a subscriber list to receive published messages of the given event category, but only those matching the given predicate (see `EventBus::subscribe_filtered`)
+    pub fn subscribe_filtered<
+        S: Subscriber<T, E> + 'static,
+        F: Fn(&E) -> bool + Send + Sync + 'static,
+    >(
+        &mut self,
+        subscriber: &Arc<RwLock<S>>,
+        to_category: T,
+        predicate: F,
+    ) -> Subscription {
+        self.bus
+            .subscribe_filtered(subscriber, to_category, predicate)
+    }
+
+    /// Adds the given `Subscriber` to the subscriber lists of each of the given event categories at once (see `EventBus::subscribe_many`)
+    pub fn subscribe_many<S: Subscriber<T, E> + 'static, I: IntoIterator<Item = T>>(
+        &mut self,
+        subscriber: &Arc<RwLock<S>>,
+        to_categories: I,
+    ) -> Subscription {
+        self.bus.subscribe_many(subscriber, to_categories)
+    }
+
+    /// Adds the given `Subscriber` to receive every event published on this `ParallelEventBus`, whatever its category (see `EventBus::subscribe_all`)
+    pub fn subscribe_all<S: Subscriber<T, E> + 'static>(
+        &mut self,
+        subscriber: &Arc<RwLock<S>>,
+    ) -> Subscription {
+        self.bus.subscribe_all(subscriber)
+    }
+
+    /// Subscribes the given closure to receive published messages of the given event category, with this `ParallelEventBus` taking ownership of it
+    ///
+    /// ### Returns
+    /// - `FnHandle<T>`: a handle which can be handed to `unsubscribe_fn` to unsubscribe the closure again.
+    pub fn subscribe_fn<F: Fn(&E) -> BusRequest + Send + Sync + 'static>(
+        &mut self,
+        to_category: T,
+        function: F,
+    ) -> FnHandle<T> {
+        self.bus.subscribe_fn(to_category, function)
+    }
+
+    /// Unsubscribes the `Subscriber` with the given id (see `Subscription::id`) from the given category on this `ParallelEventBus` (non-blocking)
+    ///
+    /// ### Notes:
+    /// - Automatically removes any dropped subscribers in the channel corresponding to the given category, if the bus encounters any.
+    ///
+    /// ### Returns
+    /// - `UnsubscribeOutcome`: whether the `Subscriber` was removed, wasn't subscribed to the given category, or had already been dropped.
+    pub fn unsubscribe(&mut self, id: SubscriberId, from_category: T) -> UnsubscribeOutcome {
+        self.bus.unsubscribe(id, from_category)
+    }
+
+    /// Unsubscribes a closure subscribed through `subscribe_fn` from this `ParallelEventBus`, dropping it (see `unsubscribe`)
+    pub fn unsubscribe_fn(&mut self, handle: &FnHandle<T>) -> UnsubscribeOutcome {
+        self.bus.unsubscribe_fn(handle)
+    }
+
+    /// Unsubscribes the given `Subscriber` from every category on this `ParallelEventBus` it's subscribed to, however many times it's subscribed to each (see `EventBus::unsubscribe_everywhere`)
+    pub fn unsubscribe_everywhere<S: ?Sized>(&mut self, subscriber: &Arc<S>) -> usize {
+        self.bus.unsubscribe_everywhere(subscriber)
+    }
+
+    /// Removes all `Subscriber`s from this `ParallelEventBus`
+    ///
+    /// ### Notes
+    /// - The memory previously allocated for the `Subscriber`s remains allocated for reuse.
+    pub fn unsubscribe_all(&mut self) {
+        self.bus.unsubscribe_all()
+    }
+
+    /// Removes all `Subscriber`s from the given category on this `ParallelEventBus`
+    pub fn unsubscribe_all_from_category(&mut self, from_category: T) {
+        self.bus.unsubscribe_all_from_category(from_category)
+    }
+
+    /// Dispatches the given event to all `Subscriber`s of that event's category at the same time, returning once every one of them has handled it (non-blocking)
+    ///
+    /// ### Notes
+    /// - Automatically removes any dropped `Subscriber`s in the channel the given event belongs to, if the bus encounters any.
+    /// - If a read-lock cannot be obtained on a given subscriber, that specific subscriber will not receive the event.
+    /// - Every subscriber receives the event, so `DoNotPropagate` has no effect (see `execute_parallel_bus_requests`).
+    pub fn dispatch_event(&mut self, event: &E) -> EventDispatchResult {
+        let result = self.dispatch_parallel(event, Wait::NonBlocking, None);
+        self.run_deferred(Wait::NonBlocking);
+        result
+    }
+
+    /// Dispatches the given event to all `Subscriber`s of that event's category at the same time, returning once every one of them has handled it (blocking)
+    ///
+    /// ### Notes
+    /// - Automatically removes any dropped `Subscriber`s in the channel the given event belongs to, if the bus encounters any.
+    /// - If a read-lock cannot be immediately obtained on a given subscriber, that specific subscriber will block its worker thread until it can be locked to receive the event.
+    /// - Every subscriber receives the event, so `DoNotPropagate` has no effect (see `execute_parallel_bus_requests`).
+    pub fn dispatch_blocking_event(&mut self, event: &E) -> EventDispatchResult {
+        let result = self.dispatch_parallel(event, Wait::Blocking, None);
+        self.run_deferred(Wait::Blocking);
+        result
+    }
+
+    /// Dispatches the given event to all `Subscriber`s of that event's category at the same time (non-blocking), noting down what became of each of them along the way
+    ///
+    /// ### Notes
+    /// - Automatically removes any dropped `Subscriber`s in the channel the given event belongs to, if the bus encounters any.
+    /// - If a read-lock cannot be obtained on a given subscriber, that specific subscriber will not receive the event.
+    /// - Subscribers are listed in the order they're subscribed in, not the order they happened to handle the event in.
+    pub fn dispatch_event_with_report(
+        &mut self,
+        event: &E,
+    ) -> (EventDispatchResult, DispatchReport) {
+        let mut report = DispatchReport::default();
+        let result = self.dispatch_parallel(event, Wait::NonBlocking, Some(&mut report));
+        self.run_deferred(Wait::NonBlocking);
+        (result, report)
+    }
+
+    /// Dispatches the given event to all `Subscriber`s of that event's category at the same time (blocking), noting down what became of each of them along the way
+    ///
+    /// ### Notes
+    /// - Automatically removes any dropped `Subscriber`s in the channel the given event belongs to, if the bus encounters any.
+    /// - If a read-lock cannot be immediately obtained on a given subscriber, that specific subscriber will block its worker thread until it can be locked to receive the event.
+    /// - Subscribers are listed in the order they're subscribed in, not the order they happened to handle the event in.
+    pub fn dispatch_blocking_event_with_report(
+        &mut self,
+        event: &E,
+    ) -> (EventDispatchResult, DispatchReport) {
+        let mut report = DispatchReport::default();
+        let result = self.dispatch_parallel(event, Wait::Blocking, Some(&mut report));
+        self.run_deferred(Wait::Blocking);
+        (result, report)
+    }
+
+    /// A handle onto this bus's queue of deferred commands, through which `Subscriber`s can publish events and change subscriptions from inside `on_event`
+    ///
+    /// ### Notes
+    /// - Commands queued while dispatching an event are run once this bus has finished dispatching it, in the order they were queued in.
+    pub fn dispatch_queue(&self) -> DispatchQueue<Self, E> {
+        self.deferred.clone()
+    }
+
+    /// Runs every command deferred by our subscribers, including any deferred along the way, until there are none left
+    ///
+    /// Events published along the way are dispatched the same way (non-blocking, blocking or up to the same deadline) as the event they followed up on
+    fn run_deferred(&mut self, wait: Wait) {
+        while let Some(command) = self.deferred.pop() {
+            match command {
+                Command::Publish(event) => {
+                    self.dispatch_parallel(&event, wait, None);
+                }
+                Command::Apply(command) => command(self),
+            }
+        }
+    }
+
+    /// Dispatches the given event through `dispatch_to_subscribers`, handing it to our dead-letter sink afterwards if it didn't reach a single subscriber, and recording it in our history
+    fn dispatch_parallel(
+        &mut self,
+        event: &E,
+        wait: Wait,
+        report: Option<&mut DispatchReport>,
+    ) -> EventDispatchResult {
+        self.bus.remove_dropped();
+        let result = if self.bus.dead_letters.is_none() {
+            self.dispatch_to_subscribers(event, wait, report)
+        } else {
+            // Whether the event is a dead letter is told by what became of it, so we need a report of our own if we weren't asked for one
+            let mut own_report = DispatchReport::default();
+            let report = report.unwrap_or(&mut own_report);
+            let result = self.dispatch_to_subscribers(event, wait, Some(&mut *report));
+            if let (Some(sink), Some(reason)) = (&self.bus.dead_letters, report.dead_letter()) {
+                sink(event, reason);
+            }
+            result
+        };
+        if let Some(history) = self.bus.history.as_mut() {
+            history.record(route(&self.bus.routing, event.categories()), event, &result);
+        }
+        result
+    }
+
+    fn dispatch_to_subscribers(
+        &mut self,
+        event: &E,
+        wait: Wait,
+        mut report: Option<&mut DispatchReport>,
+    ) -> EventDispatchResult {
+        let mut failures = None;
+        let mut handed = Handed::default();
+        let mut isolation = self.bus.isolation.as_mut();
+        let poison = self.bus.poison.as_ref();
+        let event = Arc::new(event.clone());
+        // Grab our list of subscribers for each category the event is routed to, if one exists, one category at a time
+        for category in route(&self.bus.routing, event.categories()) {
+            if let Some(subscriber_list) = self.bus.channels.get_mut(&category) {
+                let deliveries = deliver_in_parallel(
+                    &self.pool,
+                    subscriber_list,
+                    &event,
+                    wait,
+                    &mut handed,
+                    isolation.as_deref_mut(),
+                    poison,
+                );
+                if let Some(category_failures) = execute_parallel_bus_requests(
+                    subscriber_list,
+                    deliveries,
+                    report.as_deref_mut(),
+                ) {
+                    failures = Some(failures.unwrap_or(0) + category_failures);
+                }
+                handed.next_category();
+            }
+        }
+        // Subscribers of every category are handed the event once its category's subscribers are done with it
+        if !self.bus.catch_all.is_empty() {
+            let deliveries = deliver_in_parallel(
+                &self.pool,
+                &self.bus.catch_all,
+                &event,
+                wait,
+                &mut handed,
+                isolation,
+                poison,
+            );
+            if let Some(catch_all_failures) =
+                execute_parallel_bus_requests(&mut self.bus.catch_all, deliveries, report)
+            {
+                failures = Some(failures.unwrap_or(0) + catch_all_failures);
+            }
+        }
+        match failures {
+            Some(failures) => dispatch_result(false, failures),
+            None => EventDispatchResult::NotNeeded,
+        }
+    }
+}
+
+/// Hands the given event to every subscriber in the given list at the same time on the given pool, returning what became of each of them once every one of them has handled it (see `execute_parallel_bus_requests`)
+///
+/// Subscribers which panic, or whose lock was poisoned, are dealt with through the bus's panic isolation and poison policy, if it has either (see `ParallelEventBus::isolating_panics` and `ParallelEventBus::handling_poison`).
+fn deliver_in_parallel<T, E>(
+    pool: &WorkerPool,
+    subscriber_list: &[SubscriberRef<T, E>],
+    event: &Arc<E>,
+    wait: Wait,
+    handed: &mut Handed,
+    mut isolation: Option<&mut Isolation>,
+    poison: Option<&PoisonPolicy>,
+) -> Vec<Delivery>
+where
+    T: Eq + PartialEq + Hash + Clone + Send + Sync + 'static,
+    E: Event<T> + Eq + PartialEq + Hash + Clone + Send + Sync + 'static,
+{
+    // Any subscriber which never reports back panicked while handling the event
+    let mut deliveries: Vec<Delivery> = subscriber_list
+        .iter()
+        .map(|subscriber| Delivery::Failed(subscriber.id(), DispatchFailure::Panicked))
+        .collect();
+    let (sender, receiver) = mpsc::channel();
+    for (idx, subscriber) in subscriber_list.iter().enumerate() {
+        if let Some(subscriber_arc) = subscriber.upgrade() {
+            if handed.skips(subscriber.address()) {
+                // Already handed the event under another of its categories
+                deliveries[idx] = Delivery::Skipped;
+                continue;
+            }
+            if !subscriber.accepts(event) {
+                // Filters are cheap enough to run right here, rather than on a worker
+                deliveries[idx] = Delivery::Filtered(subscriber.id());
+                continue;
+            }
+            if isolation
+                .as_ref()
+                .map_or(false, |isolation| isolation.quarantines(subscriber.id()))
+            {
+                deliveries[idx] = Delivery::Skipped;
+                continue;
+            }
+            let (id, mutable, isolated, recover) = (
+                subscriber.id(),
+                subscriber.is_mutable(),
+                isolation.is_some(),
+                poison.map_or(false, PoisonPolicy::recovers),
+            );
+            let event = Arc::clone(event);
+            let sender = sender.clone();
+            pool.execute(move || {
+                let delivery = deliver_to(
+                    id,
+                    &subscriber_arc,
+                    &event,
+                    wait,
+                    mutable,
+                    isolated,
+                    recover,
+                );
+                let _ = sender.send((idx, delivery));
+            });
+        } else {
+            // Found an invalid reference to a subscriber (which was probably dropped by the owner), clean it up along with any unsubscribers
+            deliveries[idx] = Delivery::Dropped;
+        }
+    }
+    // Drop our own sender so the receiver hangs up once every worker has reported back
+    drop(sender);
+    for (idx, delivery) in receiver {
+        let delivery = match isolation.as_deref_mut() {
+            Some(isolation) => isolation.judge(delivery),
+            None => delivery,
+        };
+        deliveries[idx] = match poison {
+            Some(poison) => poison.judge(delivery),
+            None => delivery,
+        };
+    }
+    for (subscriber, delivery) in subscriber_list.iter().zip(&deliveries) {
+        handed.note(subscriber.address(), delivery);
+    }
+    deliveries
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::Mutex;
+
+    #[derive(Debug, Eq, PartialEq, Hash, Clone)]
+    enum Category {
+        Input,
+        Keyboard,
+        Window,
+    }
+
+    #[derive(Debug, Eq, PartialEq, Hash, Clone)]
+    struct TestEvent(Category, u32);
+
+    impl Event<Category> for TestEvent {
+        fn category(&self) -> Category {
+            self.0.clone()
+        }
+    }
+
+    impl HierarchicalCategory for Category {
+        fn parent(&self) -> Option<Self> {
+            match self {
+                Category::Keyboard => Some(Category::Input),
+                _ => None,
+            }
+        }
+    }
+
+    #[derive(Debug, Eq, PartialEq, Hash, Clone)]
+    struct TopicEvent(&'static str, u32);
+
+    impl Event<String> for TopicEvent {
+        fn category(&self) -> String {
+            self.0.to_string()
+        }
+    }
+
+    /// Notes down every event it's handed, answering each with the same request
+    struct Recorder {
+        received: Mutex<Vec<u32>>,
+        request: BusRequest,
+    }
+
+    impl Recorder {
+        fn new(request: BusRequest) -> Arc<RwLock<Self>> {
+            Arc::new(RwLock::new(Self {
+                received: Mutex::new(Vec::new()),
+                request,
+            }))
+        }
+    }
+
+    fn received(subscriber: &Arc<RwLock<Recorder>>) -> Vec<u32> {
+        subscriber.read().unwrap().received.lock().unwrap().clone()
+    }
+
+    impl Subscriber<Category, TestEvent> for Recorder {
+        fn on_event(&self, event: &TestEvent) -> BusRequest {
+            self.received.lock().unwrap().push(event.1);
+            self.request.clone()
+        }
+    }
+
+    impl Subscriber<String, TopicEvent> for Recorder {
+        fn on_event(&self, event: &TopicEvent) -> BusRequest {
+            self.received.lock().unwrap().push(event.1);
+            self.request.clone()
+        }
+    }
+
+    /// An event which belongs to both the `Input` and `Window` categories
+    #[derive(Debug, Eq, PartialEq, Hash, Clone)]
+    struct SharedEvent(u32);
+
+    impl Event<Category> for SharedEvent {
+        fn category(&self) -> Category {
+            Category::Input
+        }
+
+        fn categories(&self) -> Vec<Category> {
+            vec![Category::Input, Category::Window]
+        }
+    }
+
+    impl Subscriber<Category, SharedEvent> for Recorder {
+        fn on_event(&self, event: &SharedEvent) -> BusRequest {
+            self.received.lock().unwrap().push(event.0);
+            self.request.clone()
+        }
+    }
+
+    #[test]
+    fn parallel_dispatch_reaches_every_subscriber_and_counts_failures() {
+        let mut bus = ParallelEventBus::new(2);
+        let handled: Vec<_> = (0..4)
+            .map(|_| Recorder::new(BusRequest::NoActionNeeded))
+            .collect();
+        let failing = Recorder::new(BusRequest::DispatchFailed);
+        for subscriber in handled.iter().chain(Some(&failing)) {
+            bus.subscribe(subscriber, Category::Input).detach();
+        }
+        assert_eq!(
+            bus.dispatch_event(&TestEvent(Category::Input, 1)),
+            EventDispatchResult::FinishedWithFailures(1)
+        );
+        for subscriber in &handled {
+            assert_eq!(received(subscriber), vec![1]);
+        }
+        assert_eq!(
+            bus.dispatch_event(&TestEvent(Category::Window, 2)),
+            EventDispatchResult::NotNeeded
+        );
+    }
+
+    #[test]
+    fn parallel_dispatch_honours_unsubscribe_but_cannot_halt_propagation() {
+        let mut bus = ParallelEventBus::new(2);
+        let leaving = Recorder::new(BusRequest::Unsubscribe);
+        let halting = Recorder::new(BusRequest::UnsubscribeAndDoNotPropagate);
+        let staying = Recorder::new(BusRequest::NoActionNeeded);
+        for subscriber in [&leaving, &halting, &staying] {
+            bus.subscribe(subscriber, Category::Input).detach();
+        }
+        assert_eq!(
+            bus.dispatch_event(&TestEvent(Category::Input, 1)),
+            EventDispatchResult::Finished
+        );
+        assert_eq!(received(&staying), vec![1]);
+        bus.dispatch_event(&TestEvent(Category::Input, 2));
+        assert_eq!(received(&leaving), vec![1]);
+        assert_eq!(received(&halting), vec![1]);
+        assert_eq!(received(&staying), vec![1, 2]);
+        assert_eq!(bus.bus.channels[&Category::Input].len(), 1);
+    }
+
+    #[test]
+    fn do_not_propagate_halts_every_lower_priority_segment() {
+        let mut bus = PriorityEventBus::<Category, TestEvent, u8>::default();
+        let first = Recorder::new(BusRequest::DoNotPropagate);
+        let later = Recorder::new(BusRequest::NoActionNeeded);
+        bus.subscribe(&first, Category::Input, 1).detach();
+        bus.subscribe(&later, Category::Input, 2).detach();
+        assert_eq!(
+            bus.dispatch_blocking_event(&TestEvent(Category::Input, 1)),
+            EventDispatchResult::Stopped
+        );
+        assert_eq!(received(&first), vec![1]);
+        assert!(received(&later).is_empty());
+    }
+
+    #[test]
+    fn report_tells_busy_subscribers_apart_from_ones_which_failed() {
+        let mut bus = EventBus::default();
+        let busy = Recorder::new(BusRequest::NoActionNeeded);
+        let failing = Recorder::new(BusRequest::DispatchFailed);
+        let busy_subscription = bus.subscribe(&busy, Category::Input);
+        let failing_subscription = bus.subscribe(&failing, Category::Input);
+        let guard = busy.write().unwrap();
+        let (result, report) = bus.dispatch_event_with_report(&TestEvent(Category::Input, 1));
+        drop(guard);
+        assert_eq!(result, EventDispatchResult::FinishedWithFailures(2));
+        assert_eq!(report.received, vec![failing_subscription.id()]);
+        assert_eq!(
+            report.failed,
+            vec![
+                (busy_subscription.id(), DispatchFailure::LockBusy),
+                (failing_subscription.id(), DispatchFailure::Rejected)
+            ]
+        );
+    }
+
+    #[test]
+    fn parallel_report_lists_subscribers_in_subscription_order() {
+        let mut bus = ParallelEventBus::new(2);
+        let subscribers: Vec<_> = (0..4)
+            .map(|_| Recorder::new(BusRequest::NoActionNeeded))
+            .collect();
+        let subscriptions: Vec<_> = subscribers
+            .iter()
+            .map(|subscriber| bus.subscribe(subscriber, Category::Input))
+            .collect();
+        let (result, report) = bus.dispatch_event_with_report(&TestEvent(Category::Input, 1));
+        assert_eq!(result, EventDispatchResult::Finished);
+        let ids: Vec<_> = subscriptions
+            .iter()
+            .map(|subscription| subscription.id())
+            .collect();
+        assert_eq!(report.received, ids);
+    }
+
+    /// Follows up on every `Input` event by publishing a `Window` event, which comes after the `Input` event has reached everyone
+    struct Relay {
+        queue: DispatchQueue<ParallelEventBus<Category, TestEvent>, TestEvent>,
+    }
+
+    impl Subscriber<Category, TestEvent> for Relay {
+        fn on_event(&self, event: &TestEvent) -> BusRequest {
+            self.queue.publish(TestEvent(Category::Window, event.1 + 1));
+            BusRequest::NoActionNeeded
+        }
+    }
+
+    #[test]
+    fn follow_up_events_are_dispatched_after_the_current_one() {
+        let mut bus = ParallelEventBus::new(2);
+        let relay = Arc::new(RwLock::new(Relay {
+            queue: bus.dispatch_queue(),
+        }));
+        let recorder = Recorder::new(BusRequest::NoActionNeeded);
+        bus.subscribe(&relay, Category::Input).detach();
+        bus.subscribe(&recorder, Category::Input).detach();
+        bus.subscribe(&recorder, Category::Window).detach();
+        bus.dispatch_blocking_event(&TestEvent(Category::Input, 1));
+        assert_eq!(received(&recorder), vec![1, 2]);
+    }
+
+    #[test]
+    fn queued_events_are_drained_in_fifo_order() {
+        let mut bus = EventBus::default();
+        let recorder = Recorder::new(BusRequest::NoActionNeeded);
+        bus.subscribe(&recorder, Category::Input).detach();
+        for n in 1..=3 {
+            bus.queue_event(TestEvent(Category::Input, n));
+        }
+        assert!(received(&recorder).is_empty());
+        assert_eq!(bus.drain().len(), 3);
+        assert_eq!(received(&recorder), vec![1, 2, 3]);
+        assert_eq!(bus.queued_events(), 0);
+    }
+
+    /// Counts the events it's handed without any interior mutability
+    struct Counter {
+        count: u32,
+    }
+
+    impl Subscriber<Category, TestEvent> for Counter {
+        fn on_event(&self, _: &TestEvent) -> BusRequest {
+            BusRequest::DispatchFailed
+        }
+
+        fn on_event_mut(&mut self, _: &TestEvent) -> BusRequest {
+            self.count += 1;
+            BusRequest::NoActionNeeded
+        }
+    }
+
+    #[test]
+    fn mutable_subscribers_are_write_locked_for_on_event_mut() {
+        let mut bus = ParallelEventBus::new(2);
+        let counter = Arc::new(RwLock::new(Counter { count: 0 }));
+        bus.subscribe_mut(&counter, Category::Input).detach();
+        for n in 0..3 {
+            assert_eq!(
+                bus.dispatch_blocking_event(&TestEvent(Category::Input, n)),
+                EventDispatchResult::Finished
+            );
+        }
+        assert_eq!(counter.read().unwrap().count, 3);
+        let guard = counter.read().unwrap();
+        assert_eq!(
+            bus.dispatch_event(&TestEvent(Category::Input, 3)),
+            EventDispatchResult::FinishedWithFailures(1)
+        );
+        drop(guard);
+    }
+
+    #[test]
+    fn closures_subscribe_to_priority_segments() {
+        let mut bus = PriorityEventBus::<Category, TestEvent, u8>::default();
+        let later = Recorder::new(BusRequest::NoActionNeeded);
+        bus.subscribe(&later, Category::Input, 2).detach();
+        let handle = bus.subscribe_fn(Category::Input, 1, |event: &TestEvent| {
+            if event.1 == 1 {
+                BusRequest::DoNotPropagate
+            } else {
+                BusRequest::NoActionNeeded
+            }
+        });
+        assert_eq!(
+            bus.dispatch_event_with_stop(&TestEvent(Category::Input, 1)),
+            PriorityDispatchResult::Stopped(1)
+        );
+        assert_eq!(bus.unsubscribe_fn(&handle), UnsubscribeOutcome::Removed);
+        assert_eq!(bus.unsubscribe_fn(&handle), UnsubscribeOutcome::NotFound);
+        bus.dispatch_event(&TestEvent(Category::Input, 1));
+        assert_eq!(received(&later), vec![1]);
+    }
+
+    #[test]
+    fn dropping_a_subscription_unsubscribes_right_away() {
+        let mut bus = ParallelEventBus::new(2);
+        let guarded = Recorder::new(BusRequest::NoActionNeeded);
+        let detached = Recorder::new(BusRequest::NoActionNeeded);
+        let subscription = bus.subscribe(&guarded, Category::Input);
+        bus.subscribe(&detached, Category::Input).detach();
+        bus.dispatch_blocking_event(&TestEvent(Category::Input, 1));
+        subscription.unsubscribe();
+        let (_, report) = bus.dispatch_blocking_event_with_report(&TestEvent(Category::Input, 2));
+        // The entry was removed before the dispatch even began, so it isn't come across as a dropped one
+        assert_eq!(report.dropped, 0);
+        assert_eq!(bus.bus.channels[&Category::Input].len(), 1);
+        assert_eq!(received(&guarded), vec![1]);
+        assert_eq!(received(&detached), vec![1, 2]);
+    }
+
+    #[test]
+    fn unsubscribe_never_waits_on_a_subscriber_lock() {
+        let mut bus = PriorityEventBus::<Category, TestEvent, u8>::default();
+        let busy = Recorder::new(BusRequest::NoActionNeeded);
+        let dead = Recorder::new(BusRequest::NoActionNeeded);
+        let busy_subscription = bus.subscribe(&busy, Category::Input, 1);
+        let busy_id = busy_subscription.id();
+        let dead_subscription = bus.subscribe(&dead, Category::Input, 1);
+        let dead_id = dead_subscription.id();
+        dead_subscription.detach();
+        drop(dead);
+        assert_eq!(
+            bus.unsubscribe(dead_id, &Category::Input, &1),
+            UnsubscribeOutcome::AlreadyDead
+        );
+        let guard = busy.write().unwrap();
+        assert_eq!(
+            bus.unsubscribe(busy_id, &Category::Input, &1),
+            UnsubscribeOutcome::Removed
+        );
+        drop(guard);
+        drop(busy_subscription);
+        assert_eq!(
+            bus.unsubscribe(busy_id, &Category::Input, &1),
+            UnsubscribeOutcome::NotFound
+        );
+        assert!(bus.channels[&Category::Input][&1].is_empty());
+    }
+
+    #[test]
+    fn busy_subscribers_leave_every_category_at_once() {
+        let mut bus = ParallelEventBus::new(2);
+        let counter = Arc::new(RwLock::new(Counter { count: 0 }));
+        bus.subscribe_mut(&counter, Category::Input).detach();
+        bus.subscribe_mut(&counter, Category::Window).detach();
+        let guard = counter.write().unwrap();
+        assert_eq!(bus.unsubscribe_everywhere(&counter), 2);
+        drop(guard);
+        assert_eq!(
+            bus.dispatch_blocking_event(&TestEvent(Category::Input, 1)),
+            EventDispatchResult::Finished
+        );
+        assert_eq!(counter.read().unwrap().count, 0);
+    }
+
+    #[test]
+    fn catch_all_subscribers_are_dispatched_to_in_parallel_too() {
+        let mut bus = ParallelEventBus::new(2);
+        let everything = Recorder::new(BusRequest::DispatchFailed);
+        let _subscription = bus.subscribe_all(&everything);
+        assert_eq!(
+            bus.dispatch_blocking_event(&TestEvent(Category::Window, 1)),
+            EventDispatchResult::FinishedWithFailures(1)
+        );
+        let input = Recorder::new(BusRequest::NoActionNeeded);
+        bus.subscribe_many(&input, vec![Category::Input]).detach();
+        let (_, report) = bus.dispatch_blocking_event_with_report(&TestEvent(Category::Input, 2));
+        assert_eq!(report.received.len(), 2);
+        assert_eq!(received(&everything), vec![1, 2]);
+        assert_eq!(received(&input), vec![2]);
+    }
+
+    #[test]
+    fn hierarchical_parallel_dispatch_reaches_parent_category_subscribers() {
+        let mut bus = ParallelEventBus::hierarchical(2, RoutingOrder::MostSpecificFirst);
+        let input = Recorder::new(BusRequest::DispatchFailed);
+        let keyboard = Recorder::new(BusRequest::NoActionNeeded);
+        let _input_subscription = bus.subscribe(&input, Category::Input);
+        let _keyboard_subscription = bus.subscribe(&keyboard, Category::Keyboard);
+        assert_eq!(
+            bus.dispatch_blocking_event(&TestEvent(Category::Keyboard, 1)),
+            EventDispatchResult::FinishedWithFailures(1)
+        );
+        assert_eq!(
+            bus.dispatch_blocking_event(&TestEvent(Category::Input, 2)),
+            EventDispatchResult::FinishedWithFailures(1)
+        );
+        assert_eq!(received(&input), vec![1, 2]);
+        assert_eq!(received(&keyboard), vec![1]);
+    }
+
+    #[test]
+    fn closures_subscribe_to_topic_filters() {
+        let mut bus = TopicEventBus::default();
+        let seen = Arc::new(Mutex::new(Vec::new()));
+        let log = Arc::clone(&seen);
+        let handle = bus
+            .subscribe_fn("plugins/+/loaded", move |event: &TopicEvent| {
+                log.lock().unwrap().push(event.1);
+                BusRequest::NoActionNeeded
+            })
+            .unwrap();
+        bus.dispatch_blocking_event(&TopicEvent("plugins/audio/loaded", 1));
+        bus.dispatch_blocking_event(&TopicEvent("plugins/audio/unloaded", 2));
+        assert_eq!(bus.unsubscribe_fn(&handle), UnsubscribeOutcome::Removed);
+        assert_eq!(
+            bus.dispatch_blocking_event(&TopicEvent("plugins/audio/loaded", 3)),
+            EventDispatchResult::NotNeeded
+        );
+        assert_eq!(*seen.lock().unwrap(), vec![1]);
+    }
+
+    #[test]
+    fn parallel_dispatch_filters_events_before_handing_them_out() {
+        let mut bus = ParallelEventBus::new(2);
+        let keyed = Recorder::new(BusRequest::NoActionNeeded);
+        let _subscription =
+            bus.subscribe_filtered(&keyed, Category::Window, |event: &TestEvent| event.1 == 7);
+        assert_eq!(
+            bus.dispatch_blocking_event(&TestEvent(Category::Window, 3)),
+            EventDispatchResult::NotNeeded
+        );
+        assert_eq!(
+            bus.dispatch_blocking_event(&TestEvent(Category::Window, 7)),
+            EventDispatchResult::Finished
+        );
+        assert_eq!(received(&keyed), vec![7]);
+    }
+
+    #[test]
+    fn parallel_dispatch_hands_events_of_several_categories_to_each_subscriber_once() {
+        let mut bus = ParallelEventBus::new(2);
+        let both = Recorder::new(BusRequest::NoActionNeeded);
+        let _many = bus.subscribe_many(&both, vec![Category::Input, Category::Window]);
+        let _all = bus.subscribe_all(&both);
+        assert_eq!(
+            bus.dispatch_blocking_event(&SharedEvent(1)),
+            EventDispatchResult::Finished
+        );
+        assert_eq!(received(&both), vec![1]);
+    }
+
+    #[test]
+    fn timed_dispatch_waits_on_busy_subscribers_until_the_deadline() {
+        let mut bus = EventBus::default();
+        let released = Recorder::new(BusRequest::NoActionNeeded);
+        let stuck = Recorder::new(BusRequest::NoActionNeeded);
+        let poisoned = Recorder::new(BusRequest::NoActionNeeded);
+        let released_subscription = bus.subscribe(&released, Category::Input);
+        let stuck_subscription = bus.subscribe(&stuck, Category::Input);
+        let poisoned_subscription = bus.subscribe(&poisoned, Category::Input);
+        let poisoner = Arc::clone(&poisoned);
+        let _ = thread::spawn(move || {
+            let _guard = poisoner.write().unwrap();
+            panic!("poisoning the subscriber's lock");
+        })
+        .join();
+        // Hold the first subscriber for a little while on another thread, and the second for the whole dispatch
+        let (locked, is_locked) = mpsc::channel();
+        let holder = Arc::clone(&released);
+        let handle = thread::spawn(move || {
+            let _guard = holder.write().unwrap();
+            locked.send(()).unwrap();
+            thread::sleep(Duration::from_millis(20));
+        });
+        is_locked.recv().unwrap();
+        let guard = stuck.write().unwrap();
+        let (result, report) = bus.dispatch_event_timeout_with_report(
+            &TestEvent(Category::Input, 1),
+            Duration::from_millis(500),
+        );
+        drop(guard);
+        handle.join().unwrap();
+        assert_eq!(result, EventDispatchResult::FinishedWithFailures(2));
+        assert_eq!(report.received, vec![released_subscription.id()]);
+        assert_eq!(
+            report.failed,
+            vec![
+                (stuck_subscription.id(), DispatchFailure::TimedOut),
+                (poisoned_subscription.id(), DispatchFailure::Poisoned)
+            ]
+        );
+        assert_eq!(received(&released), vec![1]);
+    }
+
+    #[test]
+    fn busy_subscribers_are_retried_in_order_on_the_next_dispatch() {
+        let mut bus = EventBus::default().retrying(RetryPolicy::default());
+        let busy = Recorder::new(BusRequest::NoActionNeeded);
+        let _subscription = bus.subscribe(&busy, Category::Input);
+        let guard = busy.write().unwrap();
+        bus.dispatch_event(&TestEvent(Category::Input, 1));
+        bus.dispatch_event(&TestEvent(Category::Input, 2));
+        assert_eq!(bus.pending_retries(), 2);
+        drop(guard);
+        assert_eq!(
+            bus.dispatch_event(&TestEvent(Category::Input, 3)),
+            EventDispatchResult::Finished
+        );
+        assert_eq!(received(&busy), vec![1, 2, 3]);
+        assert_eq!(bus.pending_retries(), 0);
+    }
+
+    #[test]
+    fn retries_give_up_once_out_of_attempts_or_room() {
+        let mut bus = EventBus::default().retrying(RetryPolicy {
+            max_attempts: 2,
+            max_pending: 2,
+            automatic: false,
+        });
+        let busy = Recorder::new(BusRequest::NoActionNeeded);
+        let subscription = bus.subscribe(&busy, Category::Input);
+        let guard = busy.write().unwrap();
+        for n in 1..=3 {
+            bus.dispatch_event(&TestEvent(Category::Input, n));
+        }
+        // The oldest event made room for the newest one
+        assert_eq!(bus.pending_retries(), 2);
+        let report = bus.retry_pending();
+        assert_eq!(
+            report.failed,
+            vec![(subscription.id(), DispatchFailure::LockBusy)]
+        );
+        // Its second attempt was the last one the event at the front had
+        assert_eq!(bus.pending_retries(), 1);
+        drop(guard);
+        let report = bus.retry_pending();
+        assert_eq!(report.received, vec![subscription.id()]);
+        assert_eq!(received(&busy), vec![3]);
+        assert_eq!(bus.pending_retries(), 0);
+    }
+
+    struct Panicker;
+
+    impl Subscriber<Category, TestEvent> for Panicker {
+        fn on_event(&self, _: &TestEvent) -> BusRequest {
+            panic!("subscriber failed to handle the event");
+        }
+    }
+
+    #[test]
+    fn panicking_subscribers_are_unsubscribed_without_poisoning_them() {
+        let mut bus = PriorityEventBus::default().isolating_panics(PanicPolicy::Unsubscribe);
+        let panicker = Arc::new(RwLock::new(Panicker));
+        let recorder = Recorder::new(BusRequest::NoActionNeeded);
+        let panicker_subscription = bus.subscribe_mut(&panicker, Category::Input, 0);
+        let _recorder_subscription = bus.subscribe(&recorder, Category::Input, 1);
+        let (result, report) = bus.dispatch_event_with_report(&TestEvent(Category::Input, 1));
+        assert_eq!(result, EventDispatchResult::FinishedWithFailures(1));
+        assert_eq!(
+            report.failed,
+            vec![(panicker_subscription.id(), DispatchFailure::Panicked)]
+        );
+        assert_eq!(report.unsubscribed, vec![panicker_subscription.id()]);
+        assert!(!panicker.is_poisoned());
+        assert_eq!(
+            bus.dispatch_event(&TestEvent(Category::Input, 2)),
+            EventDispatchResult::Finished
+        );
+        assert_eq!(received(&recorder), vec![1, 2]);
+    }
+
+    fn poison<S: Send + Sync + 'static>(subscriber: &Arc<RwLock<S>>) {
+        let subscriber = Arc::clone(subscriber);
+        let _ = thread::spawn(move || {
+            let _guard = subscriber.write().unwrap();
+            panic!("poisoning the subscriber's lock");
+        })
+        .join();
+    }
+
+    #[test]
+    fn poisoned_subscribers_can_be_recovered() {
+        let mut bus = EventBus::default().handling_poison(PoisonPolicy::Recover);
+        let recorder = Recorder::new(BusRequest::NoActionNeeded);
+        let _subscription = bus.subscribe(&recorder, Category::Input);
+        poison(&recorder);
+        for n in 1..=2 {
+            assert_eq!(
+                bus.dispatch_blocking_event(&TestEvent(Category::Input, n)),
+                EventDispatchResult::Finished
+            );
+        }
+        let recorder = recorder
+            .read()
+            .unwrap_or_else(|poisoned| poisoned.into_inner());
+        assert_eq!(*recorder.received.lock().unwrap(), vec![1, 2]);
+    }
+
+    #[test]
+    fn poisoned_subscribers_are_reported_and_left_subscribed() {
+        let noticed = Arc::new(Mutex::new(Vec::new()));
+        let notify = Arc::clone(&noticed);
+        let mut bus = PriorityEventBus::default().handling_poison(PoisonPolicy::Notify(Arc::new(
+            move |id| notify.lock().unwrap().push(id),
+        )));
+        let recorder = Recorder::new(BusRequest::NoActionNeeded);
+        let subscription = bus.subscribe(&recorder, Category::Input, 0);
+        poison(&recorder);
+        let (result, report) =
+            bus.dispatch_blocking_event_with_report(&TestEvent(Category::Input, 1));
+        assert_eq!(result, EventDispatchResult::FinishedWithFailures(1));
+        assert_eq!(
+            report.failed,
+            vec![(subscription.id(), DispatchFailure::Poisoned)]
+        );
+        assert!(report.unsubscribed.is_empty());
+        assert_eq!(*noticed.lock().unwrap(), vec![subscription.id()]);
+        assert_eq!(
+            bus.dispatch_blocking_event(&TestEvent(Category::Input, 2)),
+            EventDispatchResult::FinishedWithFailures(1)
+        );
+        assert_eq!(
+            *noticed.lock().unwrap(),
+            vec![subscription.id(), subscription.id()]
+        );
+    }
+
+    #[test]
+    fn parallel_dispatch_hands_dead_letters_to_the_sink() {
+        let dead_letters = Arc::new(Mutex::new(Vec::new()));
+        let sink = Arc::clone(&dead_letters);
+        let mut bus =
+            ParallelEventBus::new(2).collecting_dead_letters(move |event: &TestEvent, reason| {
+                sink.lock().unwrap().push((event.1, reason))
+            });
+        let failing = Recorder::new(BusRequest::DispatchFailed);
+        let recorder = Recorder::new(BusRequest::NoActionNeeded);
+        let failing_subscription = bus.subscribe(&failing, Category::Input);
+        let _recorder_subscription = bus.subscribe(&recorder, Category::Window);
+        let (_, report) = bus.dispatch_event_with_report(&TestEvent(Category::Input, 1));
+        assert_eq!(report.received, vec![failing_subscription.id()]);
+        bus.dispatch_event(&TestEvent(Category::Window, 2));
+        bus.dispatch_event(&TestEvent(Category::Keyboard, 3));
+        assert_eq!(
+            *dead_letters.lock().unwrap(),
+            vec![(1, DeadLetter::AllFailed), (3, DeadLetter::NoSubscribers)]
+        );
+    }
+
+    #[test]
+    fn sticky_events_are_kept_for_every_category_they_are_routed_to() {
+        let mut bus = EventBus::hierarchical(RoutingOrder::MostSpecificFirst);
+        bus.make_sticky(Category::Input);
+        bus.dispatch_event(&TestEvent(Category::Keyboard, 1));
+        // Only wants the one event it was handed on subscribing
+        let once = Recorder::new(BusRequest::Unsubscribe);
+        let _once_subscription = bus.subscribe(&once, Category::Input);
+        assert_eq!(received(&once), vec![1]);
+        assert_eq!(
+            bus.dispatch_event(&TestEvent(Category::Input, 2)),
+            EventDispatchResult::NotNeeded
+        );
+        assert_eq!(received(&once), vec![1]);
+        bus.clear_all_sticky();
+        bus.dispatch_event(&TestEvent(Category::Input, 3));
+        assert_eq!(bus.sticky_event(&Category::Input), None);
+    }
+
+    #[test]
+    fn parallel_history_keeps_the_latest_events_of_each_category() {
+        let mut bus = ParallelEventBus::new(2).recording_history(2);
+        let recorder = Recorder::new(BusRequest::DispatchFailed);
+        let _subscription = bus.subscribe(&recorder, Category::Keyboard);
+        for id in 1..=3 {
+            bus.dispatch_event(&TestEvent(Category::Keyboard, id));
+        }
+        assert_eq!(
+            bus.history(&Category::Keyboard)
+                .iter()
+                .map(|entry| (entry.sequence, entry.event.1, entry.result.clone()))
+                .collect::<Vec<_>>(),
+            vec![
+                (1, 2, EventDispatchResult::FinishedWithFailures(1)),
+                (2, 3, EventDispatchResult::FinishedWithFailures(1))
+            ]
+        );
+        assert!(bus.history(&Category::Window).is_empty());
+    }
+
+    #[test]
+    fn subscribing_with_replay_stops_once_the_subscriber_unsubscribes() {
+        let mut bus = EventBus::default().recording_history(4);
+        for id in 1..=5 {
+            bus.dispatch_event(&TestEvent(Category::Keyboard, id));
+        }
+        let recorder = Recorder::new(BusRequest::NoActionNeeded);
+        let _subscription = bus.subscribe_with_replay(&recorder, Category::Keyboard, 3);
+        assert_eq!(received(&recorder), vec![3, 4, 5]);
+        // Only wants the first event it's handed, so isn't subscribed after all
+        let once = Recorder::new(BusRequest::Unsubscribe);
+        let _once_subscription = bus.subscribe_with_replay(&once, Category::Keyboard, 3);
+        assert_eq!(received(&once), vec![3]);
+        bus.dispatch_event(&TestEvent(Category::Keyboard, 6));
+        assert_eq!(received(&recorder), vec![3, 4, 5, 6]);
+        assert_eq!(received(&once), vec![3]);
+    }
+
+    #[test]
+    fn halting_subscribers_also_subscribed_to_every_category_are_handed_the_event_once() {
+        let mut bus = EventBus::default();
+        let halting = Recorder::new(BusRequest::DoNotPropagate);
+        let _subscription = bus.subscribe(&halting, Category::Input);
+        let _all_subscription = bus.subscribe_all(&halting);
+        assert_eq!(
+            bus.dispatch_event(&TestEvent(Category::Input, 1)),
+            EventDispatchResult::Stopped
+        );
+        assert_eq!(received(&halting), vec![1]);
+        let mut priority_bus = PriorityEventBus::default();
+        let _priority_subscription = priority_bus.subscribe(&halting, Category::Input, 0);
+        let _priority_all_subscription = priority_bus.subscribe_all(&halting);
+        priority_bus.dispatch_event(&TestEvent(Category::Input, 2));
+        assert_eq!(received(&halting), vec![1, 2]);
+    }
+
+    #[test]
+    fn events_queued_for_a_busy_subscriber_are_neither_failures_nor_dead_letters() {
+        let dead_letters = Arc::new(Mutex::new(Vec::new()));
+        let mut bus = EventBus::default()
+            .retrying(RetryPolicy::default())
+            .collecting_dead_letters({
+                let dead_letters = Arc::clone(&dead_letters);
+                move |event: &TestEvent, reason| {
+                    dead_letters.lock().unwrap().push((event.1, reason))
+                }
+            });
+        let busy = Recorder::new(BusRequest::NoActionNeeded);
+        let subscription = bus.subscribe(&busy, Category::Input);
+        let guard = busy.write().unwrap();
+        for n in 1..=2 {
+            let (result, report) = bus.dispatch_event_with_report(&TestEvent(Category::Input, n));
+            assert_eq!(result, EventDispatchResult::Finished);
+            // The event from the earlier dispatch is retried (and queued up again) ahead of this one
+            assert_eq!(report.queued, vec![subscription.id(); n as usize]);
+            assert!(report.failed.is_empty());
+        }
+        drop(guard);
+        bus.retry_pending();
+        assert_eq!(received(&busy), vec![1, 2]);
+        assert!(dead_letters.lock().unwrap().is_empty());
+    }
+
+    #[test]
+    fn retried_subscribers_only_unsubscribe_from_the_category_the_event_came_through() {
+        let mut bus = EventBus::default().retrying(RetryPolicy {
+            automatic: false,
+            ..RetryPolicy::default()
+        });
+        let leaving = Recorder::new(BusRequest::Unsubscribe);
+        let subscription = bus.subscribe_many(&leaving, vec![Category::Input, Category::Window]);
+        let guard = leaving.write().unwrap();
+        bus.dispatch_event(&TestEvent(Category::Input, 1));
+        drop(guard);
+        let report = bus.retry_pending();
+        assert_eq!(report.unsubscribed, vec![subscription.id()]);
+        bus.dispatch_event(&TestEvent(Category::Input, 2));
+        bus.dispatch_event(&TestEvent(Category::Window, 3));
+        assert_eq!(received(&leaving), vec![1, 3]);
+    }
+
+    #[test]
+    fn automatic_retries_show_up_in_the_next_dispatch_report() {
+        let mut bus = EventBus::default().retrying(RetryPolicy::default());
+        let busy = Recorder::new(BusRequest::NoActionNeeded);
+        let subscription = bus.subscribe(&busy, Category::Input);
+        let guard = busy.write().unwrap();
+        bus.dispatch_event(&TestEvent(Category::Input, 1));
+        drop(guard);
+        let (result, report) = bus.dispatch_event_with_report(&TestEvent(Category::Input, 2));
+        assert_eq!(result, EventDispatchResult::Finished);
+        // Once for the retried event, then once for the new one
+        assert_eq!(report.received, vec![subscription.id(), subscription.id()]);
+        assert!(report.queued.is_empty());
+        assert_eq!(received(&busy), vec![1, 2]);
+    }
+
+    #[test]
+    fn topic_subscribers_which_panic_are_unsubscribed() {
+        let mut bus = TopicEventBus::default().isolating_panics(PanicPolicy::Unsubscribe);
+        let seen = Arc::new(Mutex::new(Vec::new()));
+        let log = Arc::clone(&seen);
+        let panicker = bus
+            .subscribe_fn("plugins/#", |_: &TopicEvent| {
+                panic!("subscriber failed to handle the event")
+            })
+            .unwrap();
+        let _recorder = bus
+            .subscribe_fn("plugins/+/loaded", move |event: &TopicEvent| {
+                log.lock().unwrap().push(event.1);
+                BusRequest::NoActionNeeded
+            })
+            .unwrap();
+        let (result, report) =
+            bus.dispatch_event_with_report(&TopicEvent("plugins/audio/loaded", 1));
+        assert_eq!(result, EventDispatchResult::FinishedWithFailures(1));
+        assert_eq!(
+            report.failed,
+            vec![(panicker.id(), DispatchFailure::Panicked)]
+        );
+        assert_eq!(report.unsubscribed, vec![panicker.id()]);
+        assert_eq!(
+            bus.dispatch_blocking_event(&TopicEvent("plugins/audio/loaded", 2)),
+            EventDispatchResult::Finished
+        );
+        assert_eq!(*seen.lock().unwrap(), vec![1, 2]);
+    }
+
+    #[test]
+    fn poisoned_topic_subscribers_are_unsubscribed() {
+        let mut bus = TopicEventBus::default().handling_poison(PoisonPolicy::Unsubscribe);
+        let poisoned = Recorder::new(BusRequest::NoActionNeeded);
+        let healthy = Recorder::new(BusRequest::NoActionNeeded);
+        let subscription = bus.subscribe(&poisoned, "sensors/#").unwrap();
+        let _healthy = bus.subscribe(&healthy, "sensors/+").unwrap();
+        poison(&poisoned);
+        let (result, report) =
+            bus.dispatch_blocking_event_with_report(&TopicEvent("sensors/hall", 1));
+        assert_eq!(result, EventDispatchResult::FinishedWithFailures(1));
+        assert_eq!(
+            report.failed,
+            vec![(subscription.id(), DispatchFailure::Poisoned)]
+        );
+        assert_eq!(report.unsubscribed, vec![subscription.id()]);
+        assert_eq!(
+            bus.dispatch_blocking_event(&TopicEvent("sensors/hall", 2)),
+            EventDispatchResult::Finished
+        );
+        assert_eq!(received(&healthy), vec![1, 2]);
+    }
+
+    #[test]
+    fn buses_can_be_shared_between_threads() {
+        let bus = Arc::new(RwLock::new(EventBus::default()));
+        let parallel = Arc::new(RwLock::new(ParallelEventBus::new(2)));
+        let recorder = Recorder::new(BusRequest::NoActionNeeded);
+        let _subscription = bus.write().unwrap().subscribe(&recorder, Category::Input);
+        let _parallel_subscription = parallel
+            .write()
+            .unwrap()
+            .subscribe(&recorder, Category::Input);
+        let handle = {
+            let (bus, parallel) = (Arc::clone(&bus), Arc::clone(&parallel));
+            thread::spawn(move || {
+                bus.write()
+                    .unwrap()
+                    .dispatch_blocking_event(&TestEvent(Category::Input, 1));
+                parallel
+                    .write()
+                    .unwrap()
+                    .dispatch_blocking_event(&TestEvent(Category::Input, 2));
+            })
+        };
+        handle.join().unwrap();
+        assert_eq!(received(&recorder), vec![1, 2]);
+    }
+
+    #[test]
+    fn failing_subscribers_stay_subscribed_without_holding_up_the_rest() {
+        let mut bus = EventBus::default();
+        let failing = Recorder::new(BusRequest::DispatchFailed);
+        let later = Recorder::new(BusRequest::NoActionNeeded);
+        let _failing = bus.subscribe(&failing, Category::Input);
+        let _later = bus.subscribe(&later, Category::Input);
+        for n in 1..=2 {
+            assert_eq!(
+                bus.dispatch_blocking_event(&TestEvent(Category::Input, n)),
+                EventDispatchResult::FinishedWithFailures(1)
+            );
+        }
+        assert_eq!(received(&failing), vec![1, 2]);
+        assert_eq!(received(&later), vec![1, 2]);
+    }
+
+    #[test]
+    fn failures_are_summed_across_priority_segments_up_to_the_one_which_stopped() {
+        let mut bus = PriorityEventBus::<Category, TestEvent, u8>::default();
+        let failing = Recorder::new(BusRequest::DispatchFailed);
+        let halting = Recorder::new(BusRequest::DoNotPropagate);
+        let never_reached = Recorder::new(BusRequest::NoActionNeeded);
+        bus.subscribe(&failing, Category::Input, 1).detach();
+        bus.subscribe(&halting, Category::Input, 2).detach();
+        bus.subscribe(&never_reached, Category::Input, 3).detach();
+        assert_eq!(
+            bus.dispatch_blocking_event_with_stop(&TestEvent(Category::Input, 1)),
+            PriorityDispatchResult::StoppedWithFailures(2, 1)
+        );
+        assert_eq!(
+            bus.dispatch_blocking_event(&TestEvent(Category::Input, 2)),
+            EventDispatchResult::Stopped
+        );
+        assert!(received(&never_reached).is_empty());
+    }
+
+    /// Follows up on every `Input` event by queueing a `Window` event for the next frame
+    struct FrameRelay {
+        queue: DispatchQueue<EventBus<Category, TestEvent>, TestEvent>,
+    }
+
+    impl Subscriber<Category, TestEvent> for FrameRelay {
+        fn on_event(&self, event: &TestEvent) -> BusRequest {
+            self.queue
+                .queue_event(TestEvent(Category::Window, event.1 + 1));
+            BusRequest::NoActionNeeded
+        }
+    }
+
+    #[test]
+    fn subscribers_queue_events_for_the_next_drain_through_the_dispatch_queue() {
+        let mut bus = EventBus::default();
+        let relay = Arc::new(RwLock::new(FrameRelay {
+            queue: bus.dispatch_queue(),
+        }));
+        let recorder = Recorder::new(BusRequest::NoActionNeeded);
+        let _relay = bus.subscribe(&relay, Category::Input);
+        let _recorder = bus.subscribe(&recorder, Category::Window);
+        bus.queue_event(TestEvent(Category::Input, 1));
+        assert_eq!(bus.drain().len(), 1);
+        assert!(received(&recorder).is_empty());
+        assert_eq!(bus.queued_events(), 1);
+        assert_eq!(
+            bus.drain(),
+            vec![(
+                TestEvent(Category::Window, 2),
+                EventDispatchResult::Finished
+            )]
+        );
+        assert_eq!(received(&recorder), vec![2]);
+    }
+
+    #[test]
+    fn dropping_a_topic_subscription_prunes_its_filter_before_the_bus_is_next_used() {
+        let mut bus = TopicEventBus::<TopicEvent>::default();
+        let recorder = Recorder::new(BusRequest::NoActionNeeded);
+        let subscription = bus.subscribe(&recorder, "sensors/+/temperature").unwrap();
+        drop(subscription);
+        bus.subscribe(&recorder, "status").unwrap().detach();
+        // Only the root's lists and those of `status` are left
+        assert_eq!(bus.channels.lists_mut().len(), 4);
+    }
+
+    #[test]
+    fn subscribers_of_overlapping_topic_filters_are_handed_each_event_once() {
+        let mut bus = TopicEventBus::default();
+        let overlapping = Recorder::new(BusRequest::NoActionNeeded);
+        let first = bus.subscribe(&overlapping, "sensors/+").unwrap();
+        let _second = bus.subscribe(&overlapping, "sensors/#").unwrap();
+        let (_, report) = bus.dispatch_blocking_event_with_report(&TopicEvent("sensors/hall", 1));
+        bus.dispatch_blocking_event(&TopicEvent("sensors/hall/humidity", 2));
+        assert_eq!(report.received, vec![first.id()]);
+        assert_eq!(received(&overlapping), vec![1, 2]);
+    }
+
+    #[test]
+    fn parallel_subscribers_which_panic_are_quarantined_without_poisoning_them() {
+        let mut bus = ParallelEventBus::new(2).isolating_panics(PanicPolicy::Quarantine);
+        let panicker = Arc::new(RwLock::new(Panicker));
+        let recorder = Recorder::new(BusRequest::NoActionNeeded);
+        let panicker_subscription = bus.subscribe(&panicker, Category::Input);
+        let _recorder_subscription = bus.subscribe(&recorder, Category::Input);
+        let (result, report) =
+            bus.dispatch_blocking_event_with_report(&TestEvent(Category::Input, 1));
+        assert_eq!(result, EventDispatchResult::FinishedWithFailures(1));
+        assert_eq!(
+            report.failed,
+            vec![(panicker_subscription.id(), DispatchFailure::Panicked)]
+        );
+        assert!(!panicker.is_poisoned());
+        assert_eq!(bus.quarantined(), vec![panicker_subscription.id()]);
+        assert_eq!(
+            bus.dispatch_blocking_event(&TestEvent(Category::Input, 2)),
+            EventDispatchResult::Finished
+        );
+        assert!(bus.release(panicker_subscription.id()));
+        assert!(bus.quarantined().is_empty());
+        assert_eq!(received(&recorder), vec![1, 2]);
+    }
+
+    #[test]
+    fn poisoned_parallel_subscribers_are_unsubscribed() {
+        let mut bus = ParallelEventBus::new(2).handling_poison(PoisonPolicy::Unsubscribe);
+        let poisoned = Recorder::new(BusRequest::NoActionNeeded);
+        let healthy = Recorder::new(BusRequest::NoActionNeeded);
+        let subscription = bus.subscribe(&poisoned, Category::Input);
+        let _healthy = bus.subscribe(&healthy, Category::Input);
+        poison(&poisoned);
+        let (result, report) =
+            bus.dispatch_blocking_event_with_report(&TestEvent(Category::Input, 1));
+        assert_eq!(result, EventDispatchResult::FinishedWithFailures(1));
+        assert_eq!(
+            report.failed,
+            vec![(subscription.id(), DispatchFailure::Poisoned)]
+        );
+        assert_eq!(report.unsubscribed, vec![subscription.id()]);
+        assert_eq!(
+            bus.dispatch_blocking_event(&TestEvent(Category::Input, 2)),
+            EventDispatchResult::Finished
+        );
+        assert_eq!(received(&healthy), vec![1, 2]);
+    }
+}
@@ -10,6 +10,11 @@ use std::hash::Hash;
 /// ### Example
 ///
 /// ```rust
+/// # use psbus::sync::Event;
+/// # #[derive(Debug, Eq, PartialEq, Hash, Clone)]
+/// # pub struct KeyboardEvent;
+/// # #[derive(Debug, Eq, PartialEq, Hash, Clone)]
+/// # pub struct MouseEvent;
 /// // TestEventType == T
 /// #[derive(Debug, Eq, PartialEq, Hash, Clone)]
 /// pub enum TestEventType {
@@ -43,4 +48,18 @@ where
     T: Eq + PartialEq + Hash + Clone + Send + Sync + 'static,
 {
     fn category(&self) -> T;
+
+    /// Every category the event belongs to, for an event which belongs to more than one (i.e. both `Input` and `Audit`)
+    ///
+    /// A bus dispatches such an event once, handing it to the `Subscriber`s of each of these categories in turn, but to each `Subscriber` at most once, and returns a single result for all of them.
+    fn categories(&self) -> Vec<T> {
+        vec![self.category()]
+    }
+
+    /// Whether an `EventBus` keeps the event around for `Subscriber`s which subscribe to one of its categories later on, even if that category isn't sticky (see `EventBus::make_sticky`)
+    ///
+    /// Defaults to `false`
+    fn is_sticky(&self) -> bool {
+        false
+    }
 }
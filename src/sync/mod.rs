@@ -1,10 +1,13 @@
 mod bus;
 mod event;
+mod pool;
 mod publish;
+mod queue;
 mod subscribe;
 pub(crate) mod types;
 
-pub use bus::EventBus;
+pub use bus::{EventBus, ParallelEventBus, PriorityEventBus, TopicEventBus};
 pub use event::Event;
 pub use publish::Publisher;
-pub use subscribe::Subscriber;
+pub use queue::DispatchQueue;
+pub use subscribe::{Subscriber, Subscription};
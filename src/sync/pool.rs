@@ -0,0 +1,77 @@
+/*
+    ABSTRACT: Definition of a fixed-size pool of worker threads, used by the parallel event bus
+    (see bus.rs) to hand a single event to many subscribers (see subscribe.rs) at the same time
+*/
+use std::panic::{catch_unwind, AssertUnwindSafe};
+use std::sync::{mpsc, Arc, Mutex};
+use std::thread::{self, JoinHandle};
+
+type Job = Box<dyn FnOnce() + Send + 'static>;
+
+/// A fixed-size pool of worker threads which pull jobs off of a shared queue
+///
+/// ### Notes
+/// - A job which panics does not take its worker down with it, the worker simply moves on to the next job.
+/// - Dropping the pool waits for every queued job to finish before joining the workers.
+pub(crate) struct WorkerPool {
+    sender: Option<mpsc::Sender<Job>>,
+    workers: Vec<JoinHandle<()>>,
+}
+
+impl WorkerPool {
+    /// Spawns a new pool of `size` worker threads (at least one)
+    pub(crate) fn new(size: usize) -> Self {
+        let (sender, receiver) = mpsc::channel::<Job>();
+        let receiver = Arc::new(Mutex::new(receiver));
+        let workers = (0..size.max(1))
+            .map(|idx| {
+                let receiver = Arc::clone(&receiver);
+                thread::Builder::new()
+                    .name(format!("psbus-worker-{}", idx))
+                    .spawn(move || loop {
+                        // Only hold the lock on the queue for as long as it takes to pull the next job off of it
+                        let job = match receiver.lock() {
+                            Ok(receiver) => receiver.recv(),
+                            Err(_) => break, // Another worker panicked while holding the queue, which should never happen
+                        };
+                        match job {
+                            // The job reports its own outcome, all we care about here is keeping this worker alive
+                            Ok(job) => {
+                                let _ = catch_unwind(AssertUnwindSafe(job));
+                            }
+                            // The pool was dropped, time to shut down
+                            Err(_) => break,
+                        }
+                    })
+                    .expect("Couldn't spawn event bus worker thread")
+            })
+            .collect();
+        Self {
+            sender: Some(sender),
+            workers,
+        }
+    }
+
+    /// The number of worker threads in this pool
+    pub(crate) fn size(&self) -> usize {
+        self.workers.len()
+    }
+
+    /// Queues the given job to be run on the next available worker thread
+    pub(crate) fn execute<F: FnOnce() + Send + 'static>(&self, job: F) {
+        if let Some(sender) = &self.sender {
+            // Sending can only fail if every worker has shut down, in which case the job is dropped along with its outcome
+            let _ = sender.send(Box::new(job));
+        }
+    }
+}
+
+impl Drop for WorkerPool {
+    fn drop(&mut self) {
+        // Closing the queue tells every worker to shut down once it runs dry
+        self.sender.take();
+        for worker in self.workers.drain(..) {
+            let _ = worker.join();
+        }
+    }
+}
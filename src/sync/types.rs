@@ -1,7 +1,467 @@
-use crate::sync::Subscriber;
-use std::collections::{BTreeMap, HashMap};
-use std::sync::{RwLock, Weak};
+use crate::{
+    sync::{Event, Subscriber, Subscription},
+    types::{
+        BusRequest, DeadLetter, Delivery, DispatchFailure, DispatchReport, Isolation, PoisonPolicy,
+        RetryPolicy, SubscriberId,
+    },
+};
+use std::collections::{BTreeMap, HashMap, VecDeque};
+use std::hash::Hash;
+use std::panic::{catch_unwind, AssertUnwindSafe};
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::{Arc, Mutex, PoisonError, RwLock, TryLockError, TryLockResult, Weak};
+use std::thread;
+use std::time::{Duration, Instant};
 
-pub(crate) type SubscriberMap<T, E> = HashMap<T, Vec<Weak<RwLock<dyn Subscriber<T, E>>>>>;
-pub(crate) type PrioritySubscriberMap<T, E, P> =
-    HashMap<T, BTreeMap<P, Vec<Weak<RwLock<dyn Subscriber<T, E>>>>>>;
+/// How long a dispatch waits on a `Subscriber` whose lock is held elsewhere
+#[derive(Debug, Clone, Copy)]
+pub(crate) enum Wait {
+    /// Not at all, the `Subscriber` is skipped right away (see `EventBus::dispatch_event`)
+    NonBlocking,
+    /// For as long as it takes (see `EventBus::dispatch_blocking_event`)
+    Blocking,
+    /// Until the given deadline, after which the `Subscriber` times out (see `EventBus::dispatch_event_timeout`)
+    Until(Instant),
+}
+
+pub(crate) type SubscriberMap<T, E> = HashMap<T, Vec<SubscriberRef<T, E>>>;
+pub(crate) type PrioritySubscriberMap<T, E, P> = HashMap<T, BTreeMap<P, Vec<SubscriberRef<T, E>>>>;
+/// A predicate deciding which events a `Subscriber` is handed at all, see `EventBus::subscribe_filtered`
+pub(crate) type EventFilter<E> = Arc<dyn Fn(&E) -> bool + Send + Sync>;
+/// Where a bus hands the events which don't reach a single `Subscriber`, see `EventBus::collecting_dead_letters`
+pub(crate) type DeadLetterSink<E> = Box<dyn Fn(&E, DeadLetter) + Send + Sync>;
+
+/// The ids of the `Subscription`s dropped since a bus last removed their entries, shared between that bus and every `Subscription` it hands out
+#[derive(Default)]
+pub(crate) struct DroppedSubscriptions(Arc<Mutex<Vec<SubscriberId>>>);
+
+impl DroppedSubscriptions {
+    /// Takes the ids noted down since this was last called
+    pub(crate) fn take(&self) -> Vec<SubscriberId> {
+        std::mem::take(&mut *self.0.lock().unwrap_or_else(PoisonError::into_inner))
+    }
+}
+
+/// A non-owning reference to a subscribed `Subscriber`, which also knows how that `Subscriber` wants to be handed events
+pub(crate) struct SubscriberRef<T, E> {
+    // The id the bus handed out to the `Subscriber` when it subscribed
+    id: SubscriberId,
+    subscriber: Weak<RwLock<dyn Subscriber<T, E>>>,
+    // Whether the `Subscriber` is handed events through `on_event_mut` under a write-lock (see `EventBus::subscribe_mut`), rather than `on_event` under a read-lock
+    mutable: bool,
+    // Set if the `Subscriber` is owned by the bus itself (see `EventBus::subscribe_fn`), keeping it alive until it's unsubscribed
+    _owned: Option<Arc<RwLock<dyn Subscriber<T, E>>>>,
+    // Cleared once the `Subscription` guarding this entry is dropped, at which point the `Subscriber` is as good as unsubscribed
+    subscribed: Arc<AtomicBool>,
+    // Decides which events the `Subscriber` is handed at all, if it subscribed through `subscribe_filtered`
+    filter: Option<EventFilter<E>>,
+}
+
+/// A copy of a `SubscriberRef` shares its id and `Subscription` with the original, see `EventBus::subscribe_many`
+impl<T, E> Clone for SubscriberRef<T, E> {
+    fn clone(&self) -> Self {
+        Self {
+            id: self.id,
+            subscriber: Weak::clone(&self.subscriber),
+            mutable: self.mutable,
+            _owned: self._owned.clone(),
+            subscribed: Arc::clone(&self.subscribed),
+            filter: self.filter.clone(),
+        }
+    }
+}
+
+impl<T, E> SubscriberRef<T, E>
+where
+    T: Eq + PartialEq + Hash + Clone + Send + Sync + 'static,
+    E: Event<T> + Eq + PartialEq + Hash + Clone + Send + Sync + 'static,
+{
+    pub(crate) fn new(subscriber: Weak<RwLock<dyn Subscriber<T, E>>>, mutable: bool) -> Self {
+        Self {
+            id: SubscriberId::next(),
+            subscriber,
+            mutable,
+            _owned: None,
+            subscribed: Arc::new(AtomicBool::new(true)),
+            filter: None,
+        }
+    }
+
+    /// A reference to a `Subscriber` which is owned by the bus, rather than by whoever subscribed it
+    pub(crate) fn owned(subscriber: Arc<RwLock<dyn Subscriber<T, E>>>) -> Self {
+        Self {
+            id: SubscriberId::next(),
+            subscriber: Arc::downgrade(&subscriber),
+            mutable: false,
+            _owned: Some(subscriber),
+            subscribed: Arc::new(AtomicBool::new(true)),
+            filter: None,
+        }
+    }
+
+    /// Has the `Subscriber` only handed events which match the given predicate, see `EventBus::subscribe_filtered`
+    pub(crate) fn filtered_by<F: Fn(&E) -> bool + Send + Sync + 'static>(
+        mut self,
+        predicate: F,
+    ) -> Self {
+        self.filter = Some(Arc::new(predicate));
+        self
+    }
+
+    /// A guard which unsubscribes the `Subscriber` once it's dropped, noting its id down in the given bus's `DroppedSubscriptions`
+    pub(crate) fn subscription(&self, dropped: &DroppedSubscriptions) -> Subscription {
+        Subscription::new(
+            self.id,
+            Arc::clone(&self.subscribed),
+            Arc::downgrade(&dropped.0),
+        )
+    }
+
+    fn is_subscribed(&self) -> bool {
+        self.subscribed.load(Ordering::Acquire)
+    }
+
+    /// Whether the `Subscriber` has been dropped by its owner, or the `Subscription` guarding it has been
+    pub(crate) fn is_dropped(&self) -> bool {
+        !self.is_subscribed() || self.subscriber.strong_count() == 0
+    }
+
+    /// The id the bus handed out to the `Subscriber` when it subscribed
+    pub(crate) fn id(&self) -> SubscriberId {
+        self.id
+    }
+
+    /// Where the `Subscriber` lives, which tells it apart from every other `Subscriber` however many times it's subscribed (without locking it)
+    pub(crate) fn address(&self) -> *const () {
+        Weak::as_ptr(&self.subscriber) as *const ()
+    }
+
+    /// Whether this refers to the given `Subscriber`, dropped or not, which is told apart by where it lives rather than by its id (so without locking it)
+    pub(crate) fn refers_to<S: ?Sized>(&self, subscriber: &Arc<S>) -> bool {
+        self.address() == Arc::as_ptr(subscriber) as *const ()
+    }
+
+    /// The `Subscriber` itself, if it hasn't been dropped (see `is_dropped`)
+    pub(crate) fn upgrade(&self) -> Option<Arc<RwLock<dyn Subscriber<T, E>>>> {
+        if !self.is_subscribed() {
+            return None;
+        }
+        self.subscriber.upgrade()
+    }
+
+    /// Whether the `Subscriber` wants to be handed events through `on_event_mut`
+    pub(crate) fn is_mutable(&self) -> bool {
+        self.mutable
+    }
+
+    /// Whether the `Subscriber` wants to be handed the given event at all, see `EventBus::subscribe_filtered`
+    pub(crate) fn accepts(&self, event: &E) -> bool {
+        self.filter.as_ref().map_or(true, |filter| filter(event))
+    }
+
+    /// Hands the given event to the `Subscriber`, if it hasn't been dropped (see `is_dropped` and `deliver_to`) and doesn't filter the event out
+    pub(crate) fn deliver(&self, event: &E, wait: Wait, isolated: bool, recover: bool) -> Delivery {
+        match self.upgrade() {
+            Some(_) if !self.accepts(event) => Delivery::Filtered(self.id),
+            Some(subscriber_arc) => deliver_to(
+                self.id,
+                &subscriber_arc,
+                event,
+                wait,
+                self.mutable,
+                isolated,
+                recover,
+            ),
+            // Found an invalid reference to a subscriber (which was probably dropped by the owner)
+            None => Delivery::Dropped,
+        }
+    }
+
+    /// Hands the given event to the `Subscriber` as `deliver` does, through the bus's panic isolation and poison policy if it has either (see `EventBus::isolating_panics` and `EventBus::handling_poison`)
+    pub(crate) fn deliver_guarded(
+        &self,
+        event: &E,
+        wait: Wait,
+        isolation: Option<&mut Isolation>,
+        poison: Option<&PoisonPolicy>,
+    ) -> Delivery {
+        let recover = poison.map_or(false, PoisonPolicy::recovers);
+        let delivery = match isolation {
+            Some(isolation) => {
+                isolation.deliver(self.id, || self.deliver(event, wait, true, recover))
+            }
+            None => self.deliver(event, wait, false, recover),
+        };
+        match poison {
+            Some(poison) => poison.judge(delivery),
+            None => delivery,
+        }
+    }
+
+    /// Hands the given events to the `Subscriber` one after the other before it's subscribed (see `EventBus::make_sticky` and `EventBus::subscribe_with_replay`), stopping as soon as it no longer wants them
+    ///
+    /// Returns whether the `Subscriber` should still be subscribed afterwards, i.e. it didn't unsubscribe itself, wasn't unsubscribed for panicking or poisoning, and hasn't been dropped.
+    pub(crate) fn replay(
+        &self,
+        events: &[E],
+        mut isolation: Option<&mut Isolation>,
+        poison: Option<&PoisonPolicy>,
+    ) -> bool {
+        events.iter().all(|event| {
+            !matches!(
+                self.deliver_guarded(event, Wait::NonBlocking, isolation.as_deref_mut(), poison),
+                Delivery::Handled(
+                    _,
+                    BusRequest::Unsubscribe | BusRequest::UnsubscribeAndDoNotPropagate
+                ) | Delivery::Evicted(..)
+                    | Delivery::Dropped
+            )
+        })
+    }
+}
+
+/// Hands the given event to the `Subscriber` with the given id, once it can be locked within the given `Wait`
+///
+/// A `mutable` subscriber is write-locked and handed the event through `on_event_mut`, any other is read-locked and handed it through `on_event`.
+/// An `isolated` subscriber which panics while handling the event does so while still locked, so the panic is caught before it can poison the lock (see `EventBus::isolating_panics`).
+/// A poisoned lock is taken anyway if we're to `recover` it (see `PoisonPolicy::Recover`).
+pub(crate) fn deliver_to<T, E>(
+    id: SubscriberId,
+    subscriber: &RwLock<dyn Subscriber<T, E>>,
+    event: &E,
+    wait: Wait,
+    mutable: bool,
+    isolated: bool,
+    recover: bool,
+) -> Delivery
+where
+    T: Eq + PartialEq + Hash + Clone + Send + Sync + 'static,
+    E: Event<T> + Eq + PartialEq + Hash + Clone + Send + Sync + 'static,
+{
+    if mutable {
+        let lock = match wait {
+            Wait::NonBlocking => subscriber.try_write(),
+            Wait::Blocking => subscriber.write().map_err(TryLockError::from),
+            Wait::Until(deadline) => lock_until(deadline, || subscriber.try_write()),
+        };
+        let lock = match lock {
+            Err(TryLockError::Poisoned(poisoned)) if recover => Ok(poisoned.into_inner()),
+            lock => lock,
+        };
+        match lock {
+            Ok(mut subscriber) => handle(id, isolated, || subscriber.on_event_mut(event)),
+            Err(TryLockError::WouldBlock) => Delivery::Failed(id, busy_failure(wait)),
+            Err(TryLockError::Poisoned(_)) => Delivery::Failed(id, DispatchFailure::Poisoned),
+        }
+    } else {
+        let lock = match wait {
+            Wait::NonBlocking => subscriber.try_read(),
+            Wait::Blocking => subscriber.read().map_err(TryLockError::from),
+            Wait::Until(deadline) => lock_until(deadline, || subscriber.try_read()),
+        };
+        let lock = match lock {
+            Err(TryLockError::Poisoned(poisoned)) if recover => Ok(poisoned.into_inner()),
+            lock => lock,
+        };
+        match lock {
+            Ok(subscriber) => handle(id, isolated, || subscriber.on_event(event)),
+            Err(TryLockError::WouldBlock) => Delivery::Failed(id, busy_failure(wait)),
+            Err(TryLockError::Poisoned(_)) => Delivery::Failed(id, DispatchFailure::Poisoned),
+        }
+    }
+}
+
+/// Has a locked `Subscriber` handle an event through `on_event`, catching any panic along the way if it's `isolated`
+fn handle<F: FnOnce() -> BusRequest>(id: SubscriberId, isolated: bool, on_event: F) -> Delivery {
+    if !isolated {
+        return Delivery::Handled(id, on_event());
+    }
+    match catch_unwind(AssertUnwindSafe(on_event)) {
+        Ok(request) => Delivery::Handled(id, request),
+        Err(_) => Delivery::Failed(id, DispatchFailure::Panicked),
+    }
+}
+
+/// Keeps trying to take a lock through `try_lock` until it's taken, turns out to be poisoned, or the given deadline passes
+///
+/// Backs off a little more after every failed attempt (up to a millisecond), rather than spinning on the lock.
+fn lock_until<G>(
+    deadline: Instant,
+    mut try_lock: impl FnMut() -> TryLockResult<G>,
+) -> TryLockResult<G> {
+    let mut backoff = Duration::from_micros(10);
+    loop {
+        match try_lock() {
+            Err(TryLockError::WouldBlock) => {
+                let now = Instant::now();
+                if now >= deadline {
+                    return Err(TryLockError::WouldBlock);
+                }
+                thread::sleep(backoff.min(deadline - now));
+                backoff = (backoff * 2).min(Duration::from_millis(1));
+            }
+            result => return result,
+        }
+    }
+}
+
+/// Why a `Subscriber` whose lock was still held elsewhere failed, given how long the dispatch was willing to wait on it
+fn busy_failure(wait: Wait) -> DispatchFailure {
+    match wait {
+        Wait::Until(_) => DispatchFailure::TimedOut,
+        Wait::NonBlocking | Wait::Blocking => DispatchFailure::LockBusy,
+    }
+}
+
+/// Events a non-blocking dispatch couldn't hand to a busy `Subscriber`, waiting to be retried (see `EventBus::retrying`)
+struct Pending<T, E> {
+    subscriber: SubscriberRef<T, E>,
+    // Each event along with the category it came through (`None` for every category, see `EventBus::subscribe_all`) and how many times it has been tried so far, oldest first
+    events: VecDeque<(E, Option<T>, u32)>,
+}
+
+/// Every `Subscriber` with events waiting to be retried, in the order they were first found busy in
+pub(crate) struct RetryQueue<T, E> {
+    policy: RetryPolicy,
+    pending: Vec<Pending<T, E>>,
+}
+
+impl<T, E> RetryQueue<T, E>
+where
+    T: Eq + PartialEq + Hash + Clone + Send + Sync + 'static,
+    E: Event<T> + Eq + PartialEq + Hash + Clone + Send + Sync + 'static,
+{
+    pub(crate) fn new(policy: RetryPolicy) -> Self {
+        Self {
+            policy,
+            pending: Vec::new(),
+        }
+    }
+
+    /// Whether pending events are retried at the start of every non-blocking dispatch
+    pub(crate) fn is_automatic(&self) -> bool {
+        self.policy.automatic
+    }
+
+    /// The number of events waiting to be retried, whichever `Subscriber` they're waiting on
+    pub(crate) fn len(&self) -> usize {
+        self.pending
+            .iter()
+            .map(|pending| pending.events.len())
+            .sum()
+    }
+
+    /// Hands the given event to the `Subscriber` through `deliver` (as a non-blocking dispatch would), queueing it up to be retried if the `Subscriber` is busy
+    ///
+    /// A `Subscriber` which still has events waiting isn't handed a new one at all, it's queued behind them so that the `Subscriber` sees every event in order.
+    /// Either way, a queued event comes out as `Delivery::Queued` rather than a failure, unless there's no room to queue it at all.
+    pub(crate) fn deliver<F: FnOnce() -> Delivery>(
+        &mut self,
+        subscriber: &SubscriberRef<T, E>,
+        origin: Option<&T>,
+        event: &E,
+        deliver: F,
+    ) -> Delivery {
+        let waiting = self
+            .pending
+            .iter()
+            .any(|pending| pending.subscriber.id() == subscriber.id());
+        let delivery = if waiting && !subscriber.is_dropped() && subscriber.accepts(event) {
+            Delivery::Failed(subscriber.id(), DispatchFailure::LockBusy)
+        } else {
+            deliver()
+        };
+        match delivery {
+            Delivery::Failed(id, DispatchFailure::LockBusy)
+                if self.push(subscriber, origin, event) =>
+            {
+                Delivery::Queued(id)
+            }
+            delivery => delivery,
+        }
+    }
+
+    /// Queues the given event, which came through the given category, behind any others waiting on the `Subscriber`, returning whether our policy leaves room to queue events at all
+    fn push(&mut self, subscriber: &SubscriberRef<T, E>, origin: Option<&T>, event: &E) -> bool {
+        if self.policy.max_attempts <= 1 || self.policy.max_pending == 0 {
+            return false;
+        }
+        let idx = match self
+            .pending
+            .iter()
+            .position(|pending| pending.subscriber.id() == subscriber.id())
+        {
+            Some(idx) => idx,
+            None => {
+                self.pending.push(Pending {
+                    subscriber: subscriber.clone(),
+                    events: VecDeque::new(),
+                });
+                self.pending.len() - 1
+            }
+        };
+        let events = &mut self.pending[idx].events;
+        if events.len() >= self.policy.max_pending {
+            events.pop_front();
+        }
+        events.push_back((event.clone(), origin.cloned(), 1));
+        true
+    }
+
+    /// Retries every pending event through `deliver`, oldest first for each `Subscriber`, noting down what became of each attempt
+    ///
+    /// A `Subscriber` which is still busy keeps the rest of its events waiting until the next retry (showing up as `Delivery::Queued`), and an event which has run out of attempts is given up on (showing up as a failure).
+    /// Events waiting on a `Subscriber` which has been dropped, or is no longer subscribed to the category they came through according to `is_subscribed`, are given up on right away.
+    ///
+    /// Returns every `Subscriber` which is to be unsubscribed from the category an event came through, having asked to be (or been evicted) while handling it.
+    pub(crate) fn retry<F, D>(
+        &mut self,
+        is_subscribed: F,
+        mut deliver: D,
+        report: &mut DispatchReport,
+    ) -> Vec<(SubscriberId, Option<T>)>
+    where
+        F: Fn(SubscriberId, Option<&T>) -> bool,
+        D: FnMut(&SubscriberRef<T, E>, &E) -> Delivery,
+    {
+        let max_attempts = self.policy.max_attempts;
+        let mut unsubscribed = Vec::new();
+        for pending in self.pending.iter_mut() {
+            let id = pending.subscriber.id();
+            pending
+                .events
+                .retain(|(_, origin, _)| is_subscribed(id, origin.as_ref()));
+            while let Some((event, origin, attempts)) = pending.events.pop_front() {
+                let delivery = match deliver(&pending.subscriber, &event) {
+                    Delivery::Failed(id, DispatchFailure::LockBusy)
+                        if attempts + 1 < max_attempts =>
+                    {
+                        Delivery::Queued(id)
+                    }
+                    delivery => delivery,
+                };
+                report.record(&delivery, false);
+                match delivery {
+                    Delivery::Queued(_) => {
+                        pending.events.push_front((event, origin, attempts + 1));
+                        break;
+                    }
+                    Delivery::Failed(_, DispatchFailure::LockBusy) => break,
+                    Delivery::Handled(
+                        _,
+                        BusRequest::Unsubscribe | BusRequest::UnsubscribeAndDoNotPropagate,
+                    )
+                    | Delivery::Evicted(..) => {
+                        // Only the subscription the event came through goes, along with the rest of the events which came through it
+                        pending.events.retain(|(_, other, _)| *other != origin);
+                        unsubscribed.push((id, origin));
+                    }
+                    Delivery::Dropped => pending.events.clear(),
+                    _ => {}
+                }
+            }
+        }
+        self.pending.retain(|pending| !pending.events.is_empty());
+        unsubscribed
+    }
+}
@@ -0,0 +1,165 @@
+/*
+    ABSTRACT: Definition of the trie the topic buses (see rc/bus.rs and sync/bus.rs) keep their
+    subscribers in, matching `/`-separated topics against MQTT-style topic filters
+*/
+use crate::types::TopicFilterError;
+use std::collections::HashMap;
+
+/// One level of a parsed topic filter
+pub(crate) enum Level<'a> {
+    /// Matches a topic level with exactly this name
+    Exact(&'a str),
+    /// `+`, matches any single topic level
+    SingleWildcard,
+    /// `#`, matches whatever is left of the topic, including nothing at all
+    MultiWildcard,
+}
+
+/// Splits the given topic filter into its levels, making sure every wildcard in it is where it's allowed to be
+pub(crate) fn parse_filter(filter: &str) -> Result<Vec<Level<'_>>, TopicFilterError> {
+    let names: Vec<&str> = filter.split('/').collect();
+    let mut levels = Vec::with_capacity(names.len());
+    for (idx, name) in names.iter().enumerate() {
+        levels.push(match *name {
+            "+" => Level::SingleWildcard,
+            "#" if idx == names.len() - 1 => Level::MultiWildcard,
+            "#" => return Err(TopicFilterError::MisplacedMultiLevelWildcard),
+            name if name.contains(['+', '#']) => return Err(TopicFilterError::PartialWildcard),
+            name => Level::Exact(name),
+        });
+    }
+    Ok(levels)
+}
+
+/// Whether the given topic matches the given parsed topic filter
+pub(crate) fn filter_matches(filter: &[Level], topic: &str) -> bool {
+    let levels: Vec<&str> = topic.split('/').collect();
+    levels_match(filter, &levels)
+}
+
+fn levels_match(filter: &[Level], topic: &[&str]) -> bool {
+    match (filter.split_first(), topic.split_first()) {
+        (Some((Level::MultiWildcard, _)), _) | (None, None) => true,
+        (Some((Level::SingleWildcard, filter)), Some((_, topic))) => levels_match(filter, topic),
+        (Some((Level::Exact(name), filter)), Some((level, topic))) => {
+            name == level && levels_match(filter, topic)
+        }
+        _ => false,
+    }
+}
+
+/// A trie of subscriber lists `R`, one per topic filter, which a topic can be matched against level by level
+pub(crate) struct TopicTrie<R> {
+    // Subscribers whose filter ends at this level
+    subscribers: Vec<R>,
+    // Subscribers whose filter ends in a `#` right after this level
+    remaining: Vec<R>,
+    // The levels below this one, by name
+    children: HashMap<String, TopicTrie<R>>,
+    // The level below this one which any name matches, i.e. `+`
+    any: Option<Box<TopicTrie<R>>>,
+}
+
+impl<R> Default for TopicTrie<R> {
+    fn default() -> Self {
+        Self {
+            subscribers: Vec::new(),
+            remaining: Vec::new(),
+            children: HashMap::new(),
+            any: None,
+        }
+    }
+}
+
+impl<R> TopicTrie<R> {
+    /// The subscriber list of the given filter, creating it (and every level leading up to it) if it doesn't exist yet
+    pub(crate) fn list_mut(&mut self, filter: &[Level]) -> &mut Vec<R> {
+        match filter.split_first() {
+            None => &mut self.subscribers,
+            Some((Level::MultiWildcard, _)) => &mut self.remaining,
+            Some((Level::SingleWildcard, rest)) => {
+                self.any.get_or_insert_with(Box::default).list_mut(rest)
+            }
+            Some((Level::Exact(name), rest)) => self
+                .children
+                .entry((*name).to_string())
+                .or_default()
+                .list_mut(rest),
+        }
+    }
+
+    /// The subscriber list of the given filter, if it exists
+    pub(crate) fn find_mut(&mut self, filter: &[Level]) -> Option<&mut Vec<R>> {
+        match filter.split_first() {
+            None => Some(&mut self.subscribers),
+            Some((Level::MultiWildcard, _)) => Some(&mut self.remaining),
+            Some((Level::SingleWildcard, rest)) => self.any.as_mut()?.find_mut(rest),
+            Some((Level::Exact(name), rest)) => self.children.get_mut(*name)?.find_mut(rest),
+        }
+    }
+
+    /// Every subscriber list in the trie, whichever filter it belongs to
+    pub(crate) fn lists_mut(&mut self) -> Vec<&mut Vec<R>> {
+        let mut lists = vec![&mut self.subscribers, &mut self.remaining];
+        for child in self.children.values_mut() {
+            lists.extend(child.lists_mut());
+        }
+        if let Some(any) = self.any.as_mut() {
+            lists.extend(any.lists_mut());
+        }
+        lists
+    }
+
+    /// Hands every non-empty subscriber list whose filter matches the given topic to `visit`, until `visit` returns true
+    ///
+    /// At each level of the topic, lists of filters naming that level come first, followed by those of filters with a `+` there, followed by those of filters ending in a `#` there.
+    ///
+    /// ### Returns
+    /// - `bool`: whether `visit` returned true, cutting the rest of the lists off.
+    pub(crate) fn visit_matching<F: FnMut(&mut Vec<R>) -> bool>(
+        &mut self,
+        topic: &[&str],
+        visit: &mut F,
+    ) -> bool {
+        match topic.split_first() {
+            None => {
+                if !self.subscribers.is_empty() && visit(&mut self.subscribers) {
+                    return true;
+                }
+            }
+            Some((name, rest)) => {
+                if let Some(child) = self.children.get_mut(*name) {
+                    if child.visit_matching(rest, visit) {
+                        return true;
+                    }
+                }
+                if let Some(any) = self.any.as_mut() {
+                    if any.visit_matching(rest, visit) {
+                        return true;
+                    }
+                }
+            }
+        }
+        !self.remaining.is_empty() && visit(&mut self.remaining)
+    }
+
+    /// Removes every level of the trie below this one which no longer leads to any subscribers
+    ///
+    /// ### Returns
+    /// - `bool`: whether this level itself no longer leads to any subscribers.
+    pub(crate) fn prune(&mut self) -> bool {
+        self.children.retain(|_, child| !child.prune());
+        if self.any.as_mut().map_or(false, |any| any.prune()) {
+            self.any = None;
+        }
+        self.subscribers.is_empty()
+            && self.remaining.is_empty()
+            && self.children.is_empty()
+            && self.any.is_none()
+    }
+
+    /// Removes every subscriber list from the trie
+    pub(crate) fn clear(&mut self) {
+        *self = Self::default();
+    }
+}
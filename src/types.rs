@@ -1,4 +1,11 @@
+use std::borrow::Borrow;
+use std::collections::{HashMap, HashSet, VecDeque};
+use std::fmt;
 use std::hash::Hash;
+use std::panic::{catch_unwind, AssertUnwindSafe};
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::Arc;
+use std::time::SystemTime;
 
 /// The response given by a `Subscriber`'s `on_event` method, which can also act as a request to the `EventBus`.
 #[derive(Debug, Eq, PartialEq, Hash, Clone)]
@@ -14,8 +21,9 @@ unsafe impl Sync for BusRequest {}
 
 /// The end result of the `EventBus`'s `dispatch_event` method, which results in one of the following:
 ///
-///     1. `Stopped`: The event was handled by some subscribers in the list, but propagation was halted before the end of the list.
-///     2. `Finished`: The event was handled by every subscriber in the list.
+/// 1. `NotNeeded`: No subscriber was handed the event, because none were subscribed to its category or every one of them filtered it out (see `subscribe_filtered`).
+/// 2. `Stopped`: The event was handled by some subscribers in the list, but propagation was halted before the end of the list.
+/// 3. `Finished`: The event was handled by every subscriber in the list.
 #[derive(Debug, Eq, PartialEq, Hash, Clone)]
 pub enum EventDispatchResult {
     NotNeeded,
@@ -26,49 +34,784 @@ pub enum EventDispatchResult {
 unsafe impl Send for EventDispatchResult {}
 unsafe impl Sync for EventDispatchResult {}
 
-/// Given a list of subscribers from the `EventBus`, this method runs a closure on every subscriber in that list.
+/// The end result of the `PriorityEventBus`'s `dispatch_event_with_stop` method, aggregated across every priority segment the event was dispatched to, which results in one of the following:
 ///
-/// Each of those subscribers will return a resulting `BusRequest`, which we act on accordingly before returning a final `EventDispatchResult`.
+/// 1. `NotNeeded`: No subscriber was handed the event, because none were subscribed to its category or every one of them filtered it out (see `subscribe_filtered`).
+/// 2. `Stopped`: The event was handled by some subscribers, but propagation was halted by a subscriber in the given priority segment.
+/// 3. `StoppedWithFailures`: As `Stopped`, but the given number of subscribers failed to handle the event before propagation was halted.
+/// 4. `Finished`: The event was handled by every subscriber in every priority segment.
+/// 5. `FinishedWithFailures`: The event was dispatched to every priority segment, but the given number of subscribers failed to handle it.
+#[derive(Debug, Eq, PartialEq, Hash, Clone)]
+pub enum PriorityDispatchResult<P> {
+    NotNeeded,
+    Stopped(P),
+    StoppedWithFailures(P, u32),
+    Finished,
+    FinishedWithFailures(u32),
+}
+
+impl<P> PriorityDispatchResult<P> {
+    /// The total number of subscribers which failed to handle the event, across every priority segment it was dispatched to
+    pub fn failures(&self) -> u32 {
+        match self {
+            PriorityDispatchResult::StoppedWithFailures(_, failures)
+            | PriorityDispatchResult::FinishedWithFailures(failures) => *failures,
+            _ => 0,
+        }
+    }
+
+    /// The priority segment in which propagation was halted, if it was
+    pub fn stopped_at(&self) -> Option<&P> {
+        match self {
+            PriorityDispatchResult::Stopped(priority)
+            | PriorityDispatchResult::StoppedWithFailures(priority, _) => Some(priority),
+            _ => None,
+        }
+    }
+}
+
+impl<P> From<PriorityDispatchResult<P>> for EventDispatchResult {
+    /// Flattens a `PriorityDispatchResult`, discarding the priority segment which halted propagation along with any failures preceding it
+    fn from(result: PriorityDispatchResult<P>) -> Self {
+        match result {
+            PriorityDispatchResult::NotNeeded => EventDispatchResult::NotNeeded,
+            PriorityDispatchResult::Stopped(_)
+            | PriorityDispatchResult::StoppedWithFailures(..) => EventDispatchResult::Stopped,
+            PriorityDispatchResult::Finished => EventDispatchResult::Finished,
+            PriorityDispatchResult::FinishedWithFailures(failures) => {
+                EventDispatchResult::FinishedWithFailures(failures)
+            }
+        }
+    }
+}
+
+/// The id a bus hands out to a `Subscriber` when it subscribes, which tells it apart from every other `Subscriber` of every bus
+///
+/// A `Subscriber` gets a new id every time it subscribes, see `Subscription::id` and `FnHandle::id`.
+#[derive(Debug, Eq, PartialEq, Hash, Clone, Copy, PartialOrd, Ord)]
+pub struct SubscriberId(u64);
+
+impl SubscriberId {
+    pub(crate) fn next() -> Self {
+        static NEXT_ID: AtomicU64 = AtomicU64::new(1);
+        Self(NEXT_ID.fetch_add(1, Ordering::Relaxed))
+    }
+}
+
+impl fmt::Display for SubscriberId {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "#{}", self.0)
+    }
+}
+
+/// A handle onto a closure subscribed to a bus through its `subscribe_fn`, which can be handed back to the bus's `unsubscribe_fn` to unsubscribe it
+///
+/// - `K` is whatever the closure was subscribed to, i.e. a category, or a category and priority.
+#[derive(Debug, Eq, PartialEq, Hash, Clone)]
+pub struct FnHandle<K> {
+    pub(crate) id: SubscriberId,
+    pub(crate) subscribed_to: K,
+}
+
+impl<K> FnHandle<K> {
+    /// The id the bus knows the subscribed closure by, as it appears in a `DispatchReport`
+    pub fn id(&self) -> SubscriberId {
+        self.id
+    }
+}
+
+/// The outcome of a bus's `unsubscribe` methods
+#[derive(Debug, Eq, PartialEq, Hash, Clone, Copy)]
+pub enum UnsubscribeOutcome {
+    /// The `Subscriber` was subscribed, and now it isn't
+    Removed,
+    /// No `Subscriber` with the given id was subscribed there (i.e. it was already unsubscribed, or it was dropped and the bus has since cleaned it up)
+    NotFound,
+    /// The `Subscriber` was still listed, but it had already been dropped (or its `Subscription` had), so it wasn't receiving events anymore
+    AlreadyDead,
+}
+
+impl UnsubscribeOutcome {
+    /// Whether the `Subscriber` was actually removed by this call
+    pub fn is_removed(&self) -> bool {
+        *self == UnsubscribeOutcome::Removed
+    }
+}
+
+/// A category which belongs to a broader parent category, i.e. `Input::Keyboard` belonging to `Input`
+///
+/// A bus created through its `hierarchical` constructor hands an event to the `Subscriber`s of the event's category and to those of every one of its ancestors, see `RoutingOrder`.
+pub trait HierarchicalCategory: Sized {
+    /// The category this one belongs to, or `None` for a top-level category
+    fn parent(&self) -> Option<Self>;
+}
+
+/// The order a hierarchical bus hands an event to the `Subscriber`s of the event's category and to those of the category's ancestors in
+///
+/// A `Subscriber` requesting `DoNotPropagate` keeps the event from every category after its own.
+#[derive(Debug, Eq, PartialEq, Hash, Clone, Copy)]
+pub enum RoutingOrder {
+    /// The event's own category first, then its parent, and so on up to its top-level category
+    MostSpecificFirst,
+    /// The event's top-level category first, then on down to the event's own category
+    MostGeneralFirst,
+}
+
+impl Default for RoutingOrder {
+    fn default() -> Self {
+        RoutingOrder::MostSpecificFirst
+    }
+}
+
+/// How a hierarchical bus routes an event through the ancestors of its category
+pub(crate) struct Routing<T> {
+    parent: fn(&T) -> Option<T>,
+    order: RoutingOrder,
+}
+
+impl<T: PartialEq> Routing<T> {
+    pub(crate) fn new(order: RoutingOrder) -> Self
+    where
+        T: HierarchicalCategory,
+    {
+        Self {
+            parent: T::parent,
+            order,
+        }
+    }
+
+    /// Every category an event of the given category is routed to, in the order it's routed to them in
+    pub(crate) fn route(&self, category: T) -> Vec<T> {
+        let mut route = Vec::new();
+        let mut next = Some(category);
+        while let Some(category) = next {
+            // A category which turns out to be its own ancestor ends the route, rather than sending us round in circles
+            if route.contains(&category) {
+                break;
+            }
+            next = (self.parent)(&category);
+            route.push(category);
+        }
+        if self.order == RoutingOrder::MostGeneralFirst {
+            route.reverse();
+        }
+        route
+    }
+}
+
+/// Every category an event belonging to the given categories (see `Event::categories`) is routed to by a bus with the given routing, in the order it's routed to them in, each of them once
+pub(crate) fn route<T: PartialEq>(routing: &Option<Routing<T>>, categories: Vec<T>) -> Vec<T> {
+    match routing {
+        Some(routing) => distinct(
+            categories
+                .into_iter()
+                .flat_map(|category| routing.route(category))
+                .collect(),
+        ),
+        None => distinct(categories),
+    }
+}
+
+/// The given categories, each of them once, in the order they first appear in
+pub(crate) fn distinct<T: PartialEq>(categories: Vec<T>) -> Vec<T> {
+    let mut distinct = Vec::with_capacity(categories.len());
+    for category in categories {
+        if !distinct.contains(&category) {
+            distinct.push(category);
+        }
+    }
+    distinct
+}
+
+/// Why a topic bus refused a topic filter, see `TopicEventBus::subscribe`
+#[derive(Debug, Eq, PartialEq, Hash, Clone, Copy)]
+pub enum TopicFilterError {
+    /// A level mixed a wildcard in with other characters, i.e. `sensors/temp+`
+    PartialWildcard,
+    /// A `#` wasn't the filter's last level, i.e. `sensors/#/temperature`
+    MisplacedMultiLevelWildcard,
+}
+
+impl fmt::Display for TopicFilterError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            TopicFilterError::PartialWildcard => {
+                write!(f, "a wildcard must take up a whole topic level")
+            }
+            TopicFilterError::MisplacedMultiLevelWildcard => {
+                write!(f, "`#` must be the last level of a topic filter")
+            }
+        }
+    }
+}
+
+impl std::error::Error for TopicFilterError {}
+
+/// A `Subscriber` made out of nothing but a closure, see the buses' `subscribe_fn` methods
+pub(crate) struct FnSubscriber<F> {
+    pub(crate) function: F,
+}
+
+impl<F> FnSubscriber<F> {
+    pub(crate) fn new(function: F) -> Self {
+        Self { function }
+    }
+}
+
+/// Why a `Subscriber` failed to handle an event dispatched to it
+#[derive(Debug, Eq, PartialEq, Hash, Clone, Copy)]
+pub enum DispatchFailure {
+    /// The `Subscriber` handled the event, but answered with `BusRequest::DispatchFailed`
+    Rejected,
+    /// The `Subscriber`'s lock was held elsewhere, so a non-blocking dispatch skipped it
+    LockBusy,
+    /// The `Subscriber`'s lock was poisoned by an earlier panic
+    Poisoned,
+    /// The `Subscriber` panicked while handling the event
+    Panicked,
+    /// The `Subscriber`'s lock was still held elsewhere once a timed dispatch's deadline had passed (see `dispatch_event_timeout`)
+    TimedOut,
+}
+
+/// How long a bus holds on to events which a non-blocking dispatch couldn't hand to a busy `Subscriber`, see `EventBus::retrying`
+#[derive(Debug, Eq, PartialEq, Clone, Copy)]
+pub struct RetryPolicy {
+    /// How many times in all (counting the dispatch which first found the `Subscriber` busy) an event is tried before it's given up on
+    pub max_attempts: u32,
+    /// How many events may be waiting on a single `Subscriber` at once, the oldest being given up on to make room for a new one
+    pub max_pending: usize,
+    /// Whether pending events are retried at the start of every non-blocking dispatch, rather than only by `retry_pending`
+    pub automatic: bool,
+}
+
+impl Default for RetryPolicy {
+    fn default() -> Self {
+        Self {
+            max_attempts: 3,
+            max_pending: 32,
+            automatic: true,
+        }
+    }
+}
+
+/// What a bus which isolates panics does with a `Subscriber` which panicked while handling an event, see `EventBus::isolating_panics`
+///
+/// Whatever the policy, a `Subscriber` which panicked counts as a failure, and shows up in a `DispatchReport` as `DispatchFailure::Panicked` (and in its `unsubscribed` as well, under `Unsubscribe`).
+/// A thread-safe bus catches the panic while the `Subscriber` is still locked, so it doesn't poison the `Subscriber`'s lock.
+#[derive(Debug, Eq, PartialEq, Hash, Clone, Copy)]
+pub enum PanicPolicy {
+    /// It stays subscribed, and is handed the next event as usual
+    Keep,
+    /// It's unsubscribed, as if it had answered with `BusRequest::Unsubscribe`
+    Unsubscribe,
+    /// It stays subscribed, but is passed over without being handed any more events (or showing up in a `DispatchReport`) until it's released again (see `EventBus::release`)
+    Quarantine,
+}
+
+/// What a thread-safe bus does with a `Subscriber` whose lock was poisoned by an earlier panic, see `sync::EventBus::handling_poison`
+///
+/// Unless its lock is recovered, the `Subscriber` counts as a failure, and shows up in a `DispatchReport` as `DispatchFailure::Poisoned` (and in its `unsubscribed` as well, under `Unsubscribe`).
+/// A `Subscriber` isolated from its own panics (see `sync::EventBus::isolating_panics`) never poisons its lock in the first place, so this only matters for locks poisoned elsewhere.
+#[derive(Clone)]
+pub enum PoisonPolicy {
+    /// The lock is recovered (see `PoisonError::into_inner`), and the `Subscriber` is handed the event as if it had never been poisoned
+    Recover,
+    /// The subscription which found the lock poisoned is unsubscribed, as if the `Subscriber` had answered with `BusRequest::Unsubscribe`
+    Unsubscribe,
+    /// The given callback is called with the `Subscriber`'s id, and the `Subscriber` stays subscribed, failing to handle every event its lock keeps it from (and calling the callback again) until it's unsubscribed some other way
+    Notify(Arc<dyn Fn(SubscriberId) + Send + Sync>),
+}
+
+impl PoisonPolicy {
+    /// Whether a poisoned lock is taken anyway
+    pub(crate) fn recovers(&self) -> bool {
+        matches!(self, PoisonPolicy::Recover)
+    }
+
+    /// Applies this policy to the outcome of handing an event to a subscriber, should its lock have been poisoned
+    pub(crate) fn judge(&self, delivery: Delivery) -> Delivery {
+        match (self, delivery) {
+            (PoisonPolicy::Unsubscribe, Delivery::Failed(id, DispatchFailure::Poisoned)) => {
+                Delivery::Evicted(id, DispatchFailure::Poisoned)
+            }
+            (PoisonPolicy::Notify(callback), Delivery::Failed(id, DispatchFailure::Poisoned)) => {
+                callback(id);
+                Delivery::Failed(id, DispatchFailure::Poisoned)
+            }
+            (_, delivery) => delivery,
+        }
+    }
+}
+
+/// Why an event was handed to a bus's dead-letter sink, see `EventBus::collecting_dead_letters`
+///
+/// An event is a dead letter if it didn't reach a single `Subscriber`, for either of these reasons. One every `Subscriber` filtered out (see `EventBus::subscribe_filtered`) isn't, since it was kept from them on purpose.
+/// Events published by `Subscriber`s along the way (see `EventBus::dispatch_queue`) are handed to the sink just the same.
+#[derive(Debug, Eq, PartialEq, Hash, Clone, Copy)]
+pub enum DeadLetter {
+    /// There was no `Subscriber` to hand the event to, either because none subscribed to its category (or a topic filter matching its topic) or because every one of them had been dropped
+    NoSubscribers,
+    /// Every `Subscriber` the event was handed to failed to handle it (see `DispatchFailure`)
+    AllFailed,
+}
+
+/// An event a bus dispatched, as kept in its history (see `EventBus::recording_history`)
+///
+/// An event is kept for every category it's routed to (see `EventBus::hierarchical` and `Event::categories`), events published by `Subscriber`s along the way (see `EventBus::dispatch_queue`) included.
+///
+/// A `Subscriber` subscribed through `EventBus::subscribe_with_replay` is handed the latest of them first, and isn't subscribed at all if it answers one of them with `BusRequest::Unsubscribe`, nor handed the rest of them. The sync buses hand them over without blocking, so a `Subscriber` whose lock is busy misses them.
+///
+/// `R` is the result type of the bus's `dispatch_event`, i.e. `EventDispatchResult`.
+#[derive(Debug, Eq, PartialEq, Clone)]
+pub struct HistoryEntry<E, R> {
+    /// Counts up by one with every event the bus dispatches, whichever category it belongs to, so entries kept for different categories can be put in order
+    pub sequence: u64,
+    /// When the event was dispatched
+    pub timestamp: SystemTime,
+    /// The event itself
+    pub event: E,
+    /// What became of the event once it was dispatched
+    pub result: R,
+}
+
+/// The latest events a bus dispatched to each category, at most `capacity` of them per category
+pub(crate) struct History<K, E, R> {
+    capacity: usize,
+    // The sequence number of the next event recorded
+    next_sequence: u64,
+    // Oldest first
+    entries: HashMap<K, VecDeque<HistoryEntry<E, R>>>,
+}
+
+impl<K: Eq + Hash, E: Clone, R: Clone> History<K, E, R> {
+    pub(crate) fn new(capacity: usize) -> Self {
+        Self {
+            capacity,
+            next_sequence: 0,
+            entries: HashMap::new(),
+        }
+    }
+
+    /// Records the given event and its result under each of the given categories, forgetting the oldest entry of any category which is already full
+    pub(crate) fn record(&mut self, categories: Vec<K>, event: &E, result: &R) {
+        // Nothing is kept at all, so no sequence number is used up either
+        if self.capacity == 0 {
+            return;
+        }
+        let sequence = self.next_sequence;
+        self.next_sequence += 1;
+        let timestamp = SystemTime::now();
+        for category in categories {
+            let entries = self.entries.entry(category).or_default();
+            if entries.len() == self.capacity {
+                entries.pop_front();
+            }
+            entries.push_back(HistoryEntry {
+                sequence,
+                timestamp,
+                event: event.clone(),
+                result: result.clone(),
+            });
+        }
+    }
+
+    /// Every entry kept for the given category, oldest first
+    pub(crate) fn entries<Q>(&self, category: &Q) -> Vec<&HistoryEntry<E, R>>
+    where
+        K: Borrow<Q>,
+        Q: Eq + Hash + ?Sized,
+    {
+        self.entries
+            .get(category)
+            .map_or_else(Vec::new, |entries| entries.iter().collect())
+    }
+
+    /// The latest `count` events kept for any of the categories `matches` accepts, oldest first, each of them once even if it was kept for several of them
+    pub(crate) fn latest<F: Fn(&K) -> bool>(&self, matches: F, count: usize) -> Vec<E> {
+        let mut entries: Vec<&HistoryEntry<E, R>> = self
+            .entries
+            .iter()
+            .filter(|(category, _)| matches(category))
+            .flat_map(|(_, entries)| entries.iter())
+            .collect();
+        entries.sort_by_key(|entry| entry.sequence);
+        entries.dedup_by_key(|entry| entry.sequence);
+        let skipped = entries.len().saturating_sub(count);
+        entries
+            .into_iter()
+            .skip(skipped)
+            .map(|entry| entry.event.clone())
+            .collect()
+    }
+}
+
+/// A detailed account of a single event dispatch, as returned by the buses' `dispatch_event_with_report` methods
+///
+/// ### Notes
+/// - A `Subscriber` which answered with `BusRequest::DispatchFailed` shows up in both `received` and `failed`.
+#[derive(Debug, Eq, PartialEq, Clone, Default)]
+pub struct DispatchReport {
+    /// The id of every `Subscriber` which was handed the event, in the order they were handed it
+    pub received: Vec<SubscriberId>,
+    /// The id of every `Subscriber` whose filter kept the event from it (see `subscribe_filtered`)
+    pub filtered: Vec<SubscriberId>,
+    /// Every `Subscriber` which failed to handle the event, and why
+    pub failed: Vec<(SubscriberId, DispatchFailure)>,
+    /// The id of every `Subscriber` which was busy, and had the event queued up to be retried later on rather than failing to handle it (see `EventBus::retrying`)
+    pub queued: Vec<SubscriberId>,
+    /// The id of every `Subscriber` which unsubscribed itself while handling the event
+    pub unsubscribed: Vec<SubscriberId>,
+    /// The id of the `Subscriber` which halted propagation, if one did
+    pub stopped_by: Option<SubscriberId>,
+    /// The number of dropped `Subscriber`s which were cleaned up along the way
+    pub dropped: usize,
+}
+
+impl DispatchReport {
+    /// Why the event this report accounts for is a dead letter, if it is one
+    ///
+    /// An event every `Subscriber` filtered out isn't one, since it was kept from them on purpose, and neither is one queued up to be retried on a busy `Subscriber`, since it's still on its way.
+    pub(crate) fn dead_letter(&self) -> Option<DeadLetter> {
+        let rejected = self
+            .failed
+            .iter()
+            .filter(|(_, failure)| *failure == DispatchFailure::Rejected)
+            .count();
+        if self.received.len() > rejected || !self.queued.is_empty() {
+            None
+        } else if !self.failed.is_empty() {
+            Some(DeadLetter::AllFailed)
+        } else if self.filtered.is_empty() {
+            Some(DeadLetter::NoSubscribers)
+        } else {
+            None
+        }
+    }
+
+    /// Appends everything the given report accounts for to what this one does, as if both had been noted down in one go (i.e. retries run ahead of a dispatch, see `EventBus::retrying`)
+    pub(crate) fn extend(&mut self, other: DispatchReport) {
+        self.received.extend(other.received);
+        self.filtered.extend(other.filtered);
+        self.failed.extend(other.failed);
+        self.queued.extend(other.queued);
+        self.unsubscribed.extend(other.unsubscribed);
+        self.stopped_by = self.stopped_by.or(other.stopped_by);
+        self.dropped += other.dropped;
+    }
+
+    /// Notes down the outcome of handing the event to a single `Subscriber`
+    ///
+    /// - `can_halt` is `false` when propagation can't be halted (i.e. in parallel dispatch), so that `stopped_by` is only set by a `Subscriber` which actually stopped it.
+    pub(crate) fn record(&mut self, delivery: &Delivery, can_halt: bool) {
+        match delivery {
+            Delivery::Handled(id, request) => {
+                self.received.push(*id);
+                match request {
+                    BusRequest::NoActionNeeded => {}
+                    BusRequest::Unsubscribe => self.unsubscribed.push(*id),
+                    BusRequest::DoNotPropagate => {
+                        if can_halt {
+                            self.stopped_by = Some(*id);
+                        }
+                    }
+                    BusRequest::UnsubscribeAndDoNotPropagate => {
+                        self.unsubscribed.push(*id);
+                        if can_halt {
+                            self.stopped_by = Some(*id);
+                        }
+                    }
+                    BusRequest::DispatchFailed => {
+                        self.failed.push((*id, DispatchFailure::Rejected))
+                    }
+                }
+            }
+            Delivery::Failed(id, failure) => self.failed.push((*id, *failure)),
+            Delivery::Evicted(id, failure) => {
+                self.failed.push((*id, *failure));
+                self.unsubscribed.push(*id);
+            }
+            Delivery::Queued(id) => self.queued.push(*id),
+            Delivery::Filtered(id) => self.filtered.push(*id),
+            Delivery::Skipped => {}
+            Delivery::Dropped => self.dropped += 1,
+        }
+    }
+}
+
+/// The outcome of handing an event to a single `Subscriber` in a channel
+#[derive(Debug, Clone)]
+pub(crate) enum Delivery {
+    /// The `Subscriber` with the given id handled the event, and responded with the given `BusRequest`
+    Handled(SubscriberId, BusRequest),
+    /// The `Subscriber` with the given id couldn't handle the event
+    Failed(SubscriberId, DispatchFailure),
+    /// The `Subscriber` with the given id couldn't handle the event, and is unsubscribed for it (see `PanicPolicy::Unsubscribe` and `PoisonPolicy::Unsubscribe`)
+    Evicted(SubscriberId, DispatchFailure),
+    /// The `Subscriber` with the given id was busy, so the event was queued up to be retried later on (see `EventBus::retrying`)
+    Queued(SubscriberId),
+    /// The `Subscriber` with the given id filtered the event out, so it was never handed it
+    Filtered(SubscriberId),
+    /// The `Subscriber` was already handed the event under another of its categories, see `Handed`
+    Skipped,
+    /// The `Subscriber` was dropped by its owner
+    Dropped,
+}
+
+/// The subscribers an event has been handed so far, while it's dispatched to each of the categories it's routed to in turn, so that a subscriber of several of them is only handed it once (see `Event::categories`)
+///
+/// A `TopicEventBus` counts each topic filter matching the event's topic as a category of its own.
+///
+/// Subscribers are told apart by where they live, so one subscribed to each category separately is still only handed the event once.
+/// One subscribed to the same category more than once is still handed the event once per subscription, as it would be if the event belonged to that category alone.
+#[derive(Default)]
+pub(crate) struct Handed {
+    // Subscribers handed the event under an earlier category, by address
+    earlier: HashSet<*const ()>,
+    // Subscribers handed the event under the current category, by address
+    current: Vec<*const ()>,
+}
+
+impl Handed {
+    /// Whether the subscriber at the given address was already handed the event under an earlier category
+    pub(crate) fn skips(&self, address: *const ()) -> bool {
+        self.earlier.contains(&address)
+    }
+
+    /// Notes down the outcome of handing the event to the subscriber at the given address under the current category
+    pub(crate) fn note(&mut self, address: *const (), delivery: &Delivery) {
+        if let Delivery::Handled(..)
+        | Delivery::Failed(..)
+        | Delivery::Evicted(..)
+        | Delivery::Queued(_) = delivery
+        {
+            self.current.push(address);
+        }
+    }
+
+    /// Hands the event to the subscriber at the given address through `deliver`, unless it was already handed it under an earlier category
+    pub(crate) fn deliver<F: FnOnce() -> Delivery>(
+        &mut self,
+        address: *const (),
+        deliver: F,
+    ) -> Delivery {
+        if self.skips(address) {
+            return Delivery::Skipped;
+        }
+        let delivery = deliver();
+        self.note(address, &delivery);
+        delivery
+    }
+
+    /// Moves on to the next category the event is routed to
+    pub(crate) fn next_category(&mut self) {
+        self.earlier.extend(self.current.drain(..));
+    }
+}
+
+/// How a bus which isolates panics (see `EventBus::isolating_panics`) deals with `Subscriber`s which panic, and which of them it has quarantined
+pub(crate) struct Isolation {
+    policy: PanicPolicy,
+    // In the order they were quarantined in
+    quarantined: Vec<SubscriberId>,
+}
+
+impl Isolation {
+    pub(crate) fn new(policy: PanicPolicy) -> Self {
+        Self {
+            policy,
+            quarantined: Vec::new(),
+        }
+    }
+
+    /// Hands the event to the subscriber with the given id through `deliver`, unless it's quarantined, applying our policy to it should it panic
+    pub(crate) fn deliver<F: FnOnce() -> Delivery>(
+        &mut self,
+        id: SubscriberId,
+        deliver: F,
+    ) -> Delivery {
+        if self.quarantines(id) {
+            return Delivery::Skipped;
+        }
+        self.judge(
+            catch_unwind(AssertUnwindSafe(deliver))
+                .unwrap_or(Delivery::Failed(id, DispatchFailure::Panicked)),
+        )
+    }
+
+    /// Whether the subscriber with the given id is quarantined, and so isn't to be handed the event
+    pub(crate) fn quarantines(&self, id: SubscriberId) -> bool {
+        self.quarantined.contains(&id)
+    }
+
+    /// Applies our policy to the outcome of handing the event to a subscriber, should it have panicked
+    pub(crate) fn judge(&mut self, delivery: Delivery) -> Delivery {
+        match delivery {
+            Delivery::Failed(id, DispatchFailure::Panicked) => match self.policy {
+                PanicPolicy::Keep => delivery,
+                PanicPolicy::Unsubscribe => Delivery::Evicted(id, DispatchFailure::Panicked),
+                PanicPolicy::Quarantine => {
+                    self.quarantined.push(id);
+                    delivery
+                }
+            },
+            delivery => delivery,
+        }
+    }
+
+    /// The id of every quarantined subscriber, in the order they were quarantined in
+    pub(crate) fn quarantined(&self) -> Vec<SubscriberId> {
+        self.quarantined.clone()
+    }
+
+    /// Lets the subscriber with the given id be handed events again, returning whether it was quarantined at all
+    pub(crate) fn release(&mut self, id: SubscriberId) -> bool {
+        let before = self.quarantined.len();
+        self.quarantined.retain(|quarantined| *quarantined != id);
+        before != self.quarantined.len()
+    }
+}
+
+/// Given a list of subscribers from the `EventBus`, this method runs a closure delivering an event to every subscriber in that list, in order.
+///
+/// Each of those deliveries will result in a `Delivery`, carrying the `BusRequest` of the subscriber which handled it, which we act on accordingly (and note down in `report`, if one was asked for).
+///
+/// ### Notes
+/// - Subscribers leaving the list are all removed in the same single pass over it, so the rest keep their order without each removal costing O(n).
+///
+/// ### Returns
+/// - `Some((bool, u32))`: `true` if propagation was halted, along with the number of subscribers which failed to handle the event before we stopped.
+/// - `None`: every subscriber in the list filtered the event out (or was already handed it under another category), so none of them were handed it.
 pub(crate) fn execute_bus_requests<T, F>(
     subscribers: &mut Vec<T>,
+    mut report: Option<&mut DispatchReport>,
     mut function: F,
-) -> EventDispatchResult
+) -> Option<(bool, u32)>
 where
-    F: FnMut(&T) -> BusRequest,
+    F: FnMut(&T) -> Delivery,
 {
-    let mut idx = 0;
+    let mut passed_over = !subscribers.is_empty();
+    let mut stopped = false;
     let mut failures = 0;
-    loop {
-        if idx < subscribers.len() {
-            // Run our closure function on each subscriber
-            match function(&subscribers[idx]) {
+    subscribers.retain(|subscriber| {
+        // Once propagation is halted, the rest of the list just stays put
+        if stopped {
+            return true;
+        }
+        // Run our closure function on each subscriber
+        let delivery = function(subscriber);
+        if let Some(report) = report.as_deref_mut() {
+            report.record(&delivery, true);
+        }
+        passed_over &= matches!(delivery, Delivery::Filtered(_) | Delivery::Skipped);
+        match delivery {
+            Delivery::Handled(_, request) => match request {
                 // A return value of None lets us simply move onto the next subscriber
-                BusRequest::NoActionNeeded => idx += 1,
+                BusRequest::NoActionNeeded => true,
                 // The rest are self explanatory
-                BusRequest::Unsubscribe => {
-                    // swap_remove for O(1) operation
-                    subscribers.swap_remove(idx);
-                }
+                BusRequest::Unsubscribe => false,
                 BusRequest::DoNotPropagate => {
-                    return EventDispatchResult::Stopped;
+                    stopped = true;
+                    true
                 }
                 BusRequest::UnsubscribeAndDoNotPropagate => {
-                    subscribers.swap_remove(idx);
-                    return EventDispatchResult::Stopped;
+                    stopped = true;
+                    false
                 }
+                // The subscriber stays subscribed, and the dispatch moves on to the next one rather than handing it the event again
                 BusRequest::DispatchFailed => {
                     failures += 1;
+                    true
                 }
+            },
+            Delivery::Failed(..) => {
+                failures += 1;
+                true
             }
-        } else {
-            // We've made it to the end of our subscriber list without stopping propagation
-            if failures == 0 {
-                return EventDispatchResult::Finished;
-            } else {
-                return EventDispatchResult::FinishedWithFailures(failures);
+            Delivery::Evicted(..) => {
+                failures += 1;
+                false
             }
+            // The subscriber will be handed the event once it's retried, so it didn't fail to handle it
+            Delivery::Queued(_) => true,
+            // The subscriber didn't want this event (or already had it), but it stays subscribed for the next one
+            Delivery::Filtered(_) | Delivery::Skipped => true,
+            // Found an invalid reference to a subscriber (which was probably dropped by the owner), clean it up
+            Delivery::Dropped => false,
+        }
+    });
+    if passed_over {
+        None
+    } else {
+        Some((stopped, failures))
+    }
+}
+
+/// Turns the outcome of `execute_bus_requests` into a final `EventDispatchResult`.
+pub(crate) fn dispatch_result(stopped: bool, failures: u32) -> EventDispatchResult {
+    match (stopped, failures) {
+        (true, _) => EventDispatchResult::Stopped,
+        (false, 0) => EventDispatchResult::Finished,
+        (false, failures) => EventDispatchResult::FinishedWithFailures(failures),
+    }
+}
+
+/// Folds the outcome of every priority segment an event was dispatched to into a final `PriorityDispatchResult`.
+///
+/// - `stopped_at` is the priority segment which halted propagation, if any did, and `failures` the sum of failures across every segment.
+pub(crate) fn priority_dispatch_result<P>(
+    stopped_at: Option<P>,
+    failures: u32,
+) -> PriorityDispatchResult<P> {
+    match (stopped_at, failures) {
+        (Some(priority), 0) => PriorityDispatchResult::Stopped(priority),
+        (Some(priority), failures) => {
+            PriorityDispatchResult::StoppedWithFailures(priority, failures)
         }
+        (None, 0) => PriorityDispatchResult::Finished,
+        (None, failures) => PriorityDispatchResult::FinishedWithFailures(failures),
     }
 }
 
-// TODO: execute_parallel_bus_requests
+/// Given a list of subscribers from the `EventBus`, and the `Delivery` to each of those subscribers of an event which was handled concurrently, this method acts on those deliveries (noting them down in `report`, if one was asked for) before returning the number of subscribers which failed to handle the event, or `None` if every one of them was passed over (see `execute_bus_requests`).
+///
+/// ### Notes
+/// - `deliveries[idx]` must be the outcome of `subscribers[idx]`.
+/// - Every subscriber has already handled the event by the time we get here, so propagation can't be halted:
+///   `DoNotPropagate` is treated as `NoActionNeeded`, and `UnsubscribeAndDoNotPropagate` as `Unsubscribe`.
+pub(crate) fn execute_parallel_bus_requests<T>(
+    subscribers: &mut Vec<T>,
+    deliveries: Vec<Delivery>,
+    mut report: Option<&mut DispatchReport>,
+) -> Option<u32> {
+    let mut passed_over = !subscribers.is_empty();
+    let mut failures = 0;
+    let mut deliveries = deliveries.into_iter();
+    subscribers.retain(|_| match deliveries.next() {
+        Some(delivery) => {
+            if let Some(report) = report.as_deref_mut() {
+                report.record(&delivery, false);
+            }
+            passed_over &= matches!(delivery, Delivery::Filtered(_) | Delivery::Skipped);
+            match delivery {
+                Delivery::Handled(_, BusRequest::Unsubscribe)
+                | Delivery::Handled(_, BusRequest::UnsubscribeAndDoNotPropagate)
+                | Delivery::Dropped => false,
+                Delivery::Evicted(..) => {
+                    failures += 1;
+                    false
+                }
+                Delivery::Handled(_, BusRequest::DispatchFailed) | Delivery::Failed(..) => {
+                    failures += 1;
+                    true
+                }
+                _ => true,
+            }
+        }
+        None => true,
+    });
+    if passed_over {
+        None
+    } else {
+        Some(failures)
+    }
+}